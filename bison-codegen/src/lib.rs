@@ -3,6 +3,7 @@ extern crate proc_macro;
 use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::{quote, quote_spanned};
 use syn::parse::{Parse, ParseStream, Peek};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::*;
 
@@ -97,7 +98,11 @@ fn extract(field: &Field) -> Result<TokenStream> {
             continue;
         }
 
-        let MyMeta { extractor, arg } = attr.parse_args()?;
+        let MyMeta {
+            extractor,
+            arg,
+            validate,
+        } = attr.parse_args()?;
 
         let arg = match arg {
             Some(arg) => {
@@ -108,11 +113,20 @@ fn extract(field: &Field) -> Result<TokenStream> {
             }
         };
 
+        let validate = if validate {
+            quote_spanned! { ty.span() =>
+                .and_then(|value| ::bison::validate::validated(value).map_err(::bison::Rejection::from))
+            }
+        } else {
+            TokenStream::new()
+        };
+
         return Ok(quote_spanned! { ty.span() =>
             let result: ::std::result::Result<<#ty as ::bison::extract::Transform<_>>::Ok, ::bison::Rejection> =
                 #extractor(&req, #arg)
                     .await
-                    .map_err(::bison::Rejection::from);
+                    .map_err(::bison::Rejection::from)
+                    #validate;
 
             ::bison::extract::Transform::transform(result)?
         });
@@ -131,32 +145,667 @@ fn extract(field: &Field) -> Result<TokenStream> {
 struct MyMeta {
     extractor: Expr,
     arg: Option<Expr>,
+    validate: bool,
 }
 
 impl Parse for MyMeta {
     fn parse(input: parse::ParseStream) -> Result<Self> {
-        let extractor: Expr = syn::parse2(parse_until(input, Token![=])?)?;
+        let extractor: Expr = syn::parse2(parse_until(input, Token![=], Token![,])?)?;
 
-        let arg = if input.is_empty() {
-            None
-        } else {
+        let arg = if input.peek(Token![=]) {
             let _: Token![=] = input.parse()?;
             Some(input.parse::<Expr>()?)
+        } else {
+            None
         };
 
-        Ok(MyMeta { extractor, arg })
+        let validate = if input.is_empty() {
+            false
+        } else {
+            let _: Token![,] = input.parse()?;
+            let flag: Ident = input.parse()?;
+
+            if flag != "validate" {
+                return Err(Error::new_spanned(flag, "expected `validate`"));
+            }
+
+            true
+        };
+
+        Ok(MyMeta {
+            extractor,
+            arg,
+            validate,
+        })
     }
 }
 
-fn parse_until<E: Peek>(input: ParseStream, end: E) -> Result<TokenStream> {
+fn parse_until<E: Peek, F: Peek>(input: ParseStream, end: E, or: F) -> Result<TokenStream> {
     let mut tokens = TokenStream::new();
-    while !input.is_empty() && !input.peek(end) {
+    while !input.is_empty() && !input.peek(end) && !input.peek(or) {
         let next: TokenTree = input.parse()?;
         tokens.extend(Some(next));
     }
     Ok(tokens)
 }
 
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn derive_validate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_validate(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand_validate(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(s) => &s.fields,
+        Data::Enum(_) => return Err(Error::new_spanned(input, "expected struct, found enum")),
+        Data::Union(_) => return Err(Error::new_spanned(input, "expected struct, found union")),
+    };
+
+    let checks = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| validate_field(index, field))
+        .collect::<Result<Vec<_>>>()?;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::bison::validate::Validate for #name #ty_generics #where_clause {
+            fn validate(&self) -> ::std::result::Result<(), ::bison::validate::ValidationErrors> {
+                let mut errors = ::bison::validate::ValidationErrors::new();
+                #(#checks)*
+                errors.into_result()
+            }
+        }
+    })
+}
+
+fn validate_field(index: usize, field: &Field) -> Result<TokenStream> {
+    let (member, field_name) = match &field.ident {
+        Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+        None => (Member::Unnamed(index.into()), index.to_string()),
+    };
+
+    let mut checks = Vec::new();
+
+    for attr in &field.attrs {
+        if !attr.path.is_ident("validate") {
+            continue;
+        }
+
+        let rules = attr.parse_args_with(Punctuated::<Rule, Token![,]>::parse_terminated)?;
+
+        for rule in rules {
+            checks.push(rule.expand(&field_name)?);
+        }
+    }
+
+    if checks.is_empty() {
+        return Ok(TokenStream::new());
+    }
+
+    // optional fields are only validated if they are present
+    if is_option(&field.ty) {
+        Ok(quote! {
+            if let ::std::option::Option::Some(value) = &self.#member {
+                #(#checks)*
+            }
+        })
+    } else {
+        Ok(quote! {
+            {
+                let value = &self.#member;
+                #(#checks)*
+            }
+        })
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => ty.path.segments.last().is_some_and(|segment| {
+            segment.ident == "Option"
+                && matches!(segment.arguments, PathArguments::AngleBracketed(_))
+        }),
+        _ => false,
+    }
+}
+
+struct Rule {
+    name: Ident,
+    value: Option<Expr>,
+    args: Vec<(Ident, Expr)>,
+}
+
+impl Parse for Rule {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+        let mut value = None;
+        let mut args = Vec::new();
+
+        if input.peek(Token![=]) {
+            let _: Token![=] = input.parse()?;
+            value = Some(input.parse()?);
+        } else if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+
+            let parsed = content.parse_terminated::<_, Token![,]>(|input: ParseStream| {
+                let name: Ident = input.parse()?;
+                let _: Token![=] = input.parse()?;
+                Ok((name, input.parse::<Expr>()?))
+            })?;
+
+            args.extend(parsed);
+        }
+
+        Ok(Rule { name, value, args })
+    }
+}
+
+impl Rule {
+    fn arg(&self, name: &str) -> Option<&Expr> {
+        self.args
+            .iter()
+            .find(|(arg, _)| arg == name)
+            .map(|(_, value)| value)
+    }
+
+    fn optional_arg(&self, name: &str) -> TokenStream {
+        match self.arg(name) {
+            Some(value) => quote!(::std::option::Option::Some(#value)),
+            None => quote!(::std::option::Option::None),
+        }
+    }
+
+    fn expand(&self, field: &str) -> Result<TokenStream> {
+        let code = self.name.to_string();
+
+        let (allowed, check): (&[&str], _) = match code.as_str() {
+            "length" => {
+                if ["min", "max", "equal"]
+                    .iter()
+                    .all(|arg| self.arg(arg).is_none())
+                {
+                    return Err(Error::new_spanned(
+                        &self.name,
+                        "expected at least one of `min`, `max` or `equal`",
+                    ));
+                }
+
+                let (min, max, equal) = (
+                    self.optional_arg("min"),
+                    self.optional_arg("max"),
+                    self.optional_arg("equal"),
+                );
+
+                (
+                    &["min", "max", "equal", "message"],
+                    quote!(::bison::validate::rules::length(value, #min, #max, #equal)),
+                )
+            }
+            "range" => {
+                if self.arg("min").is_none() && self.arg("max").is_none() {
+                    return Err(Error::new_spanned(
+                        &self.name,
+                        "expected at least one of `min` or `max`",
+                    ));
+                }
+
+                let (min, max) = (self.optional_arg("min"), self.optional_arg("max"));
+
+                (
+                    &["min", "max", "message"],
+                    quote!(::bison::validate::rules::range(value, #min, #max)),
+                )
+            }
+            "email" => (&["message"], quote!(::bison::validate::rules::email(value))),
+            "regex" => {
+                let pattern = match (&self.value, self.arg("pattern")) {
+                    (Some(pattern), _) | (None, Some(pattern)) => pattern,
+                    (None, None) => {
+                        return Err(Error::new_spanned(&self.name, "expected a pattern"));
+                    }
+                };
+
+                let check = match pattern {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit), ..
+                    }) => {
+                        let expect = format!("invalid regex for `{}`", field);
+
+                        quote! {{
+                            static REGEX: ::bison::validate::__private::Lazy<::bison::validate::__private::Regex> =
+                                ::bison::validate::__private::Lazy::new(|| {
+                                    ::bison::validate::__private::Regex::new(#lit).expect(#expect)
+                                });
+
+                            ::bison::validate::rules::regex(value, &REGEX)
+                        }}
+                    }
+                    pattern => quote!(::bison::validate::rules::regex(value, &#pattern)),
+                };
+
+                (&["pattern", "message"], check)
+            }
+            "nested" => {
+                if let Some((arg, _)) = self.args.first() {
+                    return Err(Error::new_spanned(arg, "unexpected argument"));
+                }
+
+                return Ok(quote! {
+                    if let ::std::result::Result::Err(nested) = ::bison::validate::Validate::validate(value) {
+                        errors.merge(#field, nested);
+                    }
+                });
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    &self.name,
+                    "unknown rule, expected one of `length`, `range`, `regex`, `email` or `nested`",
+                ))
+            }
+        };
+
+        if let Some(value) = &self.value {
+            if code != "regex" {
+                return Err(Error::new_spanned(value, "unexpected value"));
+            }
+        }
+
+        if let Some((arg, _)) = self
+            .args
+            .iter()
+            .find(|(arg, _)| !allowed.iter().any(|allowed| arg == allowed))
+        {
+            return Err(Error::new_spanned(arg, "unexpected argument"));
+        }
+
+        Ok(match self.arg("message") {
+            Some(message) => quote! {
+                if ::std::result::Result::is_err(&#check) {
+                    errors.add(#field, #code, #message);
+                }
+            },
+            None => quote! {
+                if let ::std::result::Result::Err(message) = #check {
+                    errors.add(#field, #code, message);
+                }
+            },
+        })
+    }
+}
+
+#[proc_macro_derive(Reject, attributes(reject))]
+pub fn derive_reject(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_reject(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand_reject(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+
+    let arms = match &input.data {
+        Data::Struct(s) => vec![reject_arm(quote!(Self), &input.attrs, &s.fields, &input)?],
+        Data::Enum(e) if e.variants.is_empty() => {
+            return Err(Error::new_spanned(
+                input,
+                "enums without variants are not supported",
+            ))
+        }
+        Data::Enum(e) => e
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                reject_arm(
+                    quote!(Self::#ident),
+                    &variant.attrs,
+                    &variant.fields,
+                    variant,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?,
+        Data::Union(_) => return Err(Error::new_spanned(input, "unions are not supported")),
+    };
+
+    let (bindings, displays): (Vec<_>, Vec<_>) =
+        arms.iter().map(|arm| (&arm.bindings, &arm.display)).unzip();
+    let (paths, statuses): (Vec<_>, Vec<_>) =
+        arms.iter().map(|arm| (&arm.path, &arm.status)).unzip();
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    #(#bindings => #displays,)*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::bison::reject::Reject for #name #ty_generics #where_clause {
+            fn status(&self) -> ::bison::http::StatusCode {
+                match self {
+                    #(#paths { .. } => #statuses,)*
+                }
+            }
+
+            fn reject(self, req: &::bison::Request) -> ::bison::Response {
+                ::bison::reject::ErrorResponse::new(
+                    ::bison::reject::Reject::status(&self),
+                    ::std::string::ToString::to_string(&self),
+                )
+                .into_response(req)
+            }
+        }
+    })
+}
+
+struct RejectArm {
+    path: TokenStream,
+    bindings: TokenStream,
+    status: TokenStream,
+    display: TokenStream,
+}
+
+fn reject_arm(
+    path: TokenStream,
+    attrs: &[Attribute],
+    fields: &Fields,
+    tokens: impl quote::ToTokens,
+) -> Result<RejectArm> {
+    let mut status = None;
+    let mut message = None;
+
+    for rule in parse_rules(attrs, "reject")? {
+        match rule.name.to_string().as_str() {
+            "status" => status = Some(status_code(&rule)?),
+            "message" => match &rule.value {
+                Some(Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit), ..
+                })) => message = Some(lit.clone()),
+                _ => return Err(Error::new_spanned(&rule.name, "expected a string literal")),
+            },
+            _ => {
+                return Err(Error::new_spanned(
+                    &rule.name,
+                    "unknown argument, expected `status` or `message`",
+                ))
+            }
+        }
+    }
+
+    let status = status.ok_or_else(|| {
+        Error::new_spanned(&tokens, "missing `#[reject(status = ...)]` attribute")
+    })?;
+
+    // fields are bound by name so the message can refer to them,
+    // with tuple fields renamed from `{0}` to `{_0}`
+    let bindings = match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names),* })
+        }
+        Fields::Unnamed(fields) => {
+            let names = (0..fields.unnamed.len()).map(|i| quote::format_ident!("_{}", i));
+            quote!(#path(#(#names),*))
+        }
+        Fields::Unit => path.clone(),
+    };
+
+    let display = match message {
+        Some(message) => {
+            let message = LitStr::new(&name_positional_args(&message.value()), message.span());
+            quote!(::std::write!(f, #message))
+        }
+        None => quote! {
+            f.write_str(
+                ::bison::reject::Reject::status(self)
+                    .canonical_reason()
+                    .unwrap_or("unknown error"),
+            )
+        },
+    };
+
+    Ok(RejectArm {
+        path,
+        bindings,
+        status,
+        display,
+    })
+}
+
+fn name_positional_args(message: &str) -> String {
+    let mut named = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        named.push(c);
+
+        if c == '{' {
+            match chars.peek() {
+                Some('{') => named.push(chars.next().unwrap()),
+                Some(c) if c.is_ascii_digit() => named.push('_'),
+                _ => {}
+            }
+        }
+    }
+
+    named
+}
+
+#[proc_macro_derive(Respond, attributes(respond))]
+pub fn derive_respond(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_respond(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand_respond(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let container = RespondAttrs::parse(&input.attrs)?;
+
+    let body = match &input.data {
+        Data::Struct(s) => {
+            let value = match (&container.format, &s.fields) {
+                (Some(format), _) => format.wrap(quote!(self)),
+                (None, Fields::Unit) => quote!(()),
+                (None, fields) if fields.len() == 1 => {
+                    let member = match fields.iter().next().unwrap().ident.clone() {
+                        Some(ident) => Member::Named(ident),
+                        None => Member::Unnamed(0.into()),
+                    };
+                    quote!(self.#member)
+                }
+                (None, fields) => {
+                    return Err(Error::new_spanned(
+                        fields,
+                        "expected a single field, or a format such as `#[respond(json)]`",
+                    ))
+                }
+            };
+
+            respond_with(container.status.as_ref(), value)
+        }
+        Data::Enum(e) => {
+            let arms = e
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let attrs = RespondAttrs::parse(&variant.attrs)?;
+                    let format = attrs.format.as_ref().or(container.format.as_ref());
+                    let status = attrs.status.as_ref().or(container.status.as_ref());
+
+                    let (pattern, value) = match &variant.fields {
+                        Fields::Unit => (quote!(Self::#ident), quote!(())),
+                        Fields::Named(fields) if fields.named.len() == 1 => {
+                            let field = &fields.named[0].ident;
+                            (quote!(Self::#ident { #field: value }), quote!(value))
+                        }
+                        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                            (quote!(Self::#ident(value)), quote!(value))
+                        }
+                        fields => {
+                            return Err(Error::new_spanned(
+                                fields,
+                                "expected a unit variant or a variant with a single field",
+                            ))
+                        }
+                    };
+
+                    let value = match format {
+                        Some(format) if !variant.fields.is_empty() => format.wrap(value),
+                        _ => value,
+                    };
+
+                    let respond = respond_with(status, value);
+                    Ok(quote!(#pattern => #respond,))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => return Err(Error::new_spanned(input, "unions are not supported")),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::bison::Respond for #name #ty_generics #where_clause {
+            type Rejection = ::bison::Rejection;
+
+            fn respond(self) -> ::std::result::Result<::bison::Response, ::bison::Rejection> {
+                #body
+            }
+        }
+    })
+}
+
+fn respond_with(status: Option<&TokenStream>, value: TokenStream) -> TokenStream {
+    let value = match status {
+        Some(status) => quote!((#status, #value)),
+        None => value,
+    };
+
+    quote!(::bison::Respond::respond(#value).map_err(::bison::Rejection::new))
+}
+
+struct RespondAttrs {
+    format: Option<Format>,
+    status: Option<TokenStream>,
+}
+
+enum Format {
+    Json,
+    MsgPack,
+    Cbor,
+}
+
+impl Format {
+    fn wrap(&self, value: TokenStream) -> TokenStream {
+        match self {
+            Format::Json => quote!(::bison::respond::Json(#value)),
+            Format::MsgPack => quote!(::bison::respond::MsgPack(#value)),
+            Format::Cbor => quote!(::bison::respond::Cbor(#value)),
+        }
+    }
+}
+
+impl RespondAttrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut format = None;
+        let mut status = None;
+
+        for rule in parse_rules(attrs, "respond")? {
+            let name = rule.name.to_string();
+
+            if name != "status" {
+                if let Some(value) = &rule.value {
+                    return Err(Error::new_spanned(value, "unexpected value"));
+                }
+            }
+
+            match name.as_str() {
+                "status" => status = Some(status_code(&rule)?),
+                "json" => format = Some(Format::Json),
+                "msgpack" => format = Some(Format::MsgPack),
+                "cbor" => format = Some(Format::Cbor),
+                _ => {
+                    return Err(Error::new_spanned(
+                        &rule.name,
+                        "unknown argument, expected one of `json`, `msgpack`, `cbor` or `status`",
+                    ))
+                }
+            }
+        }
+
+        Ok(RespondAttrs { format, status })
+    }
+}
+
+fn parse_rules(attrs: &[Attribute], name: &str) -> Result<Vec<Rule>> {
+    let mut rules = Vec::new();
+
+    for attr in attrs {
+        if !attr.path.is_ident(name) {
+            continue;
+        }
+
+        let parsed = attr.parse_args_with(Punctuated::<Rule, Token![,]>::parse_terminated)?;
+
+        for rule in parsed {
+            if let Some((arg, _)) = rule.args.first() {
+                return Err(Error::new_spanned(arg, "unexpected argument"));
+            }
+
+            rules.push(rule);
+        }
+    }
+
+    Ok(rules)
+}
+
+fn status_code(rule: &Rule) -> Result<TokenStream> {
+    let lit = match &rule.value {
+        Some(Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        })) => lit,
+        _ => {
+            return Err(Error::new_spanned(
+                &rule.name,
+                "expected a status code, such as `status = 404`",
+            ))
+        }
+    };
+
+    let code = lit.base10_parse::<u16>()?;
+    if !(100..1000).contains(&code) {
+        return Err(Error::new_spanned(lit, "invalid status code"));
+    }
+
+    Ok(quote!(::bison::http::StatusCode::from_u16(#code).unwrap()))
+}
+
 #[proc_macro_attribute]
 pub fn async_trait_not_send_internal(
     _: proc_macro::TokenStream,
@@ -3,12 +3,15 @@ use std::future::{ready, Future, Ready};
 use std::io;
 use std::net::*;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::task::Context;
 use std::task::Poll;
 
+use bison::http::OnUpgrade;
+use bison::wrap::CatchPanic;
 use bison::Bison;
 use futures_core::Stream;
+use http_body::Body as _;
 use hyper::server::conn::AddrIncoming;
 use hyper::service::Service;
 
@@ -36,8 +39,15 @@ where
     }
 
     fn into_service(self) -> BisonService<W> {
+        // catch panics by default, unless the application configured it
+        let bison = if self.state::<CatchPanic>().is_some() {
+            self
+        } else {
+            self.catch_panic(CatchPanic::new())
+        };
+
         BisonService {
-            bison: Arc::new(self),
+            bison: Arc::new(bison),
         }
     }
 }
@@ -76,9 +86,18 @@ where
         Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, req: hyper::Request<hyper::Body>) -> Self::Future {
+    fn call(&mut self, mut req: hyper::Request<hyper::Body>) -> Self::Future {
+        // hand the connection to the application if it asks to switch protocols
+        if req.headers().contains_key(hyper::header::UPGRADE) {
+            let on_upgrade = hyper::upgrade::on(&mut req);
+            req.extensions_mut().insert(OnUpgrade::new(on_upgrade));
+        }
+
         let (parts, body) = req.into_parts();
-        let req = hyper::Request::from_parts(parts, bison::http::Body::stream(body));
+        let body = Arc::new(Mutex::new(body));
+        let body =
+            bison::http::Body::stream_with_trailers(HyperBody(body.clone()), HyperTrailers(body));
+        let req = hyper::Request::from_parts(parts, body);
         let bison = self.bison.clone();
 
         Box::pin(async move {
@@ -115,9 +134,9 @@ impl http_body::Body for BisonHttpBody {
 
     fn poll_trailers(
         self: Pin<&mut Self>,
-        _: &mut Context<'_>,
+        cx: &mut Context<'_>,
     ) -> Poll<Result<Option<hyper::HeaderMap>, Self::Error>> {
-        Poll::Ready(Ok(None))
+        self.inner.poll_trailers(cx)
     }
 
     fn size_hint(&self) -> http_body::SizeHint {
@@ -133,6 +152,38 @@ impl http_body::Body for BisonHttpBody {
     }
 }
 
+/// The data of a hyper request body.
+///
+/// The body is shared with [`HyperTrailers`], which reads
+/// the trailers once the data has been exhausted.
+struct HyperBody(Arc<Mutex<hyper::Body>>);
+
+impl Stream for HyperBody {
+    type Item = Result<bison::http::Bytes, hyper::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut body = self.0.lock().unwrap();
+        Pin::new(&mut *body).poll_data(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let hint = http_body::Body::size_hint(&*self.0.lock().unwrap());
+        (hint.lower() as _, hint.upper().map(|upper| upper as _))
+    }
+}
+
+/// The trailers of a hyper request body.
+struct HyperTrailers(Arc<Mutex<hyper::Body>>);
+
+impl Future for HyperTrailers {
+    type Output = Result<Option<hyper::HeaderMap>, hyper::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut body = self.0.lock().unwrap();
+        Pin::new(&mut *body).poll_trailers(cx)
+    }
+}
+
 pub trait ToSocketAddr {
     fn to_socket_addr(self) -> io::Result<SocketAddr>;
 }
@@ -1,9 +1,10 @@
 use crate::handler::{self, Context, Handler};
 use crate::http::{Body, Method, Response};
+use crate::reject::{DebugMode, ErrorFormat, OnRejection};
 use crate::router::{Router, Scope};
 use crate::state::{self, State};
-use crate::wrap::{Call, Wrap};
-use crate::Request;
+use crate::wrap::{Call, CatchPanic, Wrap};
+use crate::{Rejection, Request};
 
 /// Where everything happens.
 ///
@@ -147,6 +148,90 @@ where
         }
     }
 
+    /// Set the format of the response bodies produced by rejections.
+    ///
+    /// By default, rejections respond with an empty body. See
+    /// [`ErrorFormat`] for details.
+    ///
+    /// ```
+    /// use bison::Bison;
+    /// use bison::reject::ErrorFormat;
+    ///
+    /// let bison = Bison::new().error_format(ErrorFormat::Problem);
+    /// ```
+    pub fn error_format(self, format: ErrorFormat) -> Self {
+        self.inject(format)
+    }
+
+    /// Render detailed error pages for rejections.
+    ///
+    /// In debug mode, the body of the responses produced by rejections is
+    /// replaced with an HTML page, or JSON if the client prefers it, showing
+    /// the `Debug` output of the rejection, its [source chain](crate::Reject::source),
+    /// the matched route, route parameters, request headers, and a backtrace
+    /// if one was [captured](Rejection::backtrace). The status code and headers
+    /// of the response are kept.
+    ///
+    /// This exposes internal details of the application, and should never be
+    /// enabled in production.
+    ///
+    /// ```
+    /// use bison::Bison;
+    ///
+    /// let bison = Bison::new().debug(cfg!(debug_assertions));
+    /// ```
+    pub fn debug(self, enabled: bool) -> Self {
+        self.inject(DebugMode(enabled))
+    }
+
+    /// Catch panics in the application and reject the request instead.
+    ///
+    /// Unlike applying [`CatchPanic`] as middleware, this also catches panics
+    /// in extractors and responders. See [`CatchPanic`] for details.
+    ///
+    /// Panics are caught by default when serving the application with
+    /// [`bison_hyper`].
+    pub fn catch_panic(self, catch_panic: CatchPanic) -> Self {
+        self.inject(catch_panic)
+    }
+
+    /// Returns the state of type `T` injected into the application, if any.
+    pub fn state<T>(&self) -> Option<&T>
+    where
+        T: State,
+    {
+        self.state.get()
+    }
+
+    /// Set a handler that turns rejections into responses.
+    ///
+    /// The handler is called with every rejection returned by extractors,
+    /// middleware, handlers and responders, as well as the [`NotFound`]
+    /// rejection for requests that do not match any route, instead of
+    /// [`Rejection::reject`]. Calling [`Rejection::reject`] from the
+    /// handler falls back to the default response.
+    ///
+    /// Rejection handlers set on a [`Scope`] take precedence over this one.
+    ///
+    /// ```
+    /// use bison::{Bison, Rejection, Request, Response};
+    ///
+    /// fn on_rejection(rejection: Rejection, req: &Request) -> Response {
+    ///     eprintln!("{}: {}", req.uri(), rejection);
+    ///     rejection.reject(req)
+    /// }
+    ///
+    /// let bison = Bison::new().on_rejection(on_rejection);
+    /// ```
+    ///
+    /// [`NotFound`]: crate::reject::NotFound
+    pub fn on_rejection<F>(self, f: F) -> Self
+    where
+        F: Fn(Rejection, &Request) -> Response + Send + Sync + 'static,
+    {
+        self.inject(OnRejection::new(f))
+    }
+
     /// Wrap the application with some middleware.
     pub fn wrap<O, C>(self, wrap: O) -> Bison<impl Wrap>
     where
@@ -19,5 +19,46 @@ pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
 pub type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + 'a>>;
 pub type BoxError = Box<dyn Error>;
 
+/// A mutable memory location.
+///
+/// Values are only ever accessed from a single thread, so there is no
+/// other thread to wait for. Instead, callers must never hold a guard
+/// across an `.await` or while locking the same value again: [`lock`]
+/// panics if the lock is already held. Use [`try_lock`] when that cannot
+/// be ruled out.
+///
+/// [`lock`]: Lock::lock
+/// [`try_lock`]: Lock::try_lock
+#[derive(Default, Clone, PartialEq)]
+pub struct Lock<T>(RefCell<T>);
+
+/// A guard holding exclusive access to the value inside of a [`Lock`].
+pub type LockGuard<'a, T> = std::cell::RefMut<'a, T>;
+
+impl<T> Lock<T> {
+    /// Create a new [`Lock`] holding the given value.
+    pub fn new(value: T) -> Self {
+        Self(RefCell::new(value))
+    }
+
+    /// Acquire exclusive access to the wrapped value.
+    ///
+    /// The lock is held until the returned guard is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is already held.
+    pub fn lock(&self) -> LockGuard<'_, T> {
+        self.0.borrow_mut()
+    }
+
+    /// Attempt to acquire exclusive access to the wrapped value.
+    ///
+    /// Returns `None` if the lock is currently held elsewhere.
+    pub fn try_lock(&self) -> Option<LockGuard<'_, T>> {
+        self.0.try_borrow_mut().ok()
+    }
+}
+
 pub use bison_codegen::async_trait_not_send as async_trait;
 pub(crate) use bison_codegen::async_trait_not_send_internal as async_trait_internal;
@@ -1,8 +1,7 @@
-use std::cell::UnsafeCell;
 use std::error::Error;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
 
 use futures_core::Stream;
 
@@ -28,84 +27,65 @@ pub use async_trait::async_trait;
 /// `async-trait` but imports from `crate::`.
 pub(crate) use async_trait::async_trait as async_trait_internal;
 
-/// A thread-safe, mutable memory location with dynamically checked borrow rules.
+/// A thread-safe, mutable memory location.
+///
+/// Acquiring the lock blocks until any other holder releases it,
+/// so concurrent access from multiple tasks never panics. A panic
+/// while the lock is held does not poison it.
 #[derive(Default)]
-pub struct RefCell<T> {
-    borrowed: AtomicBool,
-    value: UnsafeCell<T>,
-}
+pub struct Lock<T>(Mutex<T>);
+
+/// A guard holding exclusive access to the value inside of a [`Lock`].
+pub type LockGuard<'a, T> = MutexGuard<'a, T>;
 
-impl<T> RefCell<T> {
-    /// Create a new [`AtomicRefCell`] holding the given value.
+impl<T> Lock<T> {
+    /// Create a new [`Lock`] holding the given value.
     pub fn new(value: T) -> Self {
-        Self {
-            borrowed: AtomicBool::new(false),
-            value: UnsafeCell::new(value),
-        }
+        Self(Mutex::new(value))
     }
 
-    /// Mutably borrows the wrapped value.
-    ///
-    /// The borrow lasts until the returned `RefMut` exit scope.
-    ///
-    /// # Panics
+    /// Acquire exclusive access to the wrapped value, blocking the
+    /// current thread until it is available.
     ///
-    /// Panics if the value is currently borrowed.
-    pub fn borrow_mut(&self) -> RefMut<'_, T> {
-        // synchronizes with the Release store in RefMut::drop
-        if self.borrowed.swap(true, Ordering::Acquire) {
-            panic!("already borrowed")
-        }
-
-        RefMut {
-            value: self.value.get(),
-            borrowed: &self.borrowed,
-        }
-    }
-}
-impl<T: Clone> Clone for RefCell<T> {
-    fn clone(&self) -> RefCell<T> {
-        RefCell::new(self.borrow_mut().clone())
+    /// The lock is held until the returned guard is dropped.
+    pub fn lock(&self) -> LockGuard<'_, T> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
     }
-}
 
-impl<T: PartialEq> PartialEq for RefCell<T> {
-    fn eq(&self, other: &RefCell<T>) -> bool {
-        *self.borrow_mut() == *other.borrow_mut()
+    /// Attempt to acquire exclusive access to the wrapped value.
+    ///
+    /// Returns `None` if the lock is currently held elsewhere.
+    pub fn try_lock(&self) -> Option<LockGuard<'_, T>> {
+        match self.0.try_lock() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(err)) => Some(err.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
     }
 }
 
-unsafe impl<T: Send> Send for RefCell<T> {}
-unsafe impl<T: Send> Sync for RefCell<T> {}
-
-/// A wrapper type for a mutably borrowed value from a [`RefCell`].
-pub struct RefMut<'b, T> {
-    value: *mut T,
-    borrowed: &'b AtomicBool,
-}
-
-impl<'b, T> Drop for RefMut<'b, T> {
-    fn drop(&mut self) {
-        // synchronizes with the Acquire load in try_borrow
-        self.borrowed.store(false, Ordering::Release)
+impl<T: Clone> Clone for Lock<T> {
+    fn clone(&self) -> Lock<T> {
+        Lock::new(self.lock().clone())
     }
 }
 
-impl<'b, T> std::ops::Deref for RefMut<'b, T> {
-    type Target = T;
-
-    fn deref(&self) -> &Self::Target {
-        // SAFETY: we have exclusive access to
-        // `value` through the `borrowed` flag
-        unsafe { &*self.value }
-    }
-}
+impl<T: PartialEq> PartialEq for Lock<T> {
+    fn eq(&self, other: &Lock<T>) -> bool {
+        // locking the same lock twice would deadlock
+        if std::ptr::eq(self, other) {
+            return true;
+        }
 
-impl<'b, T> std::ops::DerefMut for RefMut<'b, T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        // SAFETY: we have exclusive access to
-        // `value` through the `borrowed` flag
-        unsafe { &mut *self.value }
+        // always lock in address order so that two comparisons
+        // in opposite directions cannot deadlock
+        if (self as *const Self) < (other as *const Self) {
+            let this = self.lock();
+            *this == *other.lock()
+        } else {
+            let other = other.lock();
+            *self.lock() == *other
+        }
     }
 }
 
@@ -2,6 +2,8 @@
 //!
 //! ...
 
+use std::borrow::Cow;
+
 /// An extractor argument.
 ///
 /// See the [module level documentation](super) for details.
@@ -57,8 +59,8 @@ impl DefaultArgument for FieldName {
     }
 }
 
-/// The name of the parameter to be extracted by
-/// [`extract::path`](super::path()) or [`extract::query`](super::query()).
+/// The name of the parameter to be extracted by [`extract::path`](super::path()),
+/// [`extract::query`](super::query()), or `extract::cookie`.
 pub struct ParamName(pub &'static str);
 
 impl Argument<&'static str> for ParamName {
@@ -72,3 +74,21 @@ impl DefaultArgument for ParamName {
         ParamName(field_name)
     }
 }
+
+/// The name of the header to be extracted by [`extract::header`](super::header()).
+///
+/// By default the name of the field is used, with underscores replaced by
+/// dashes, so a field named `user_agent` extracts the `user-agent` header.
+pub struct HeaderName(pub Cow<'static, str>);
+
+impl Argument<&'static str> for HeaderName {
+    fn new(_: &'static str, value: &'static str) -> Self {
+        HeaderName(Cow::Borrowed(value))
+    }
+}
+
+impl DefaultArgument for HeaderName {
+    fn new(field_name: &'static str) -> Self {
+        HeaderName(Cow::Owned(field_name.replace('_', "-")))
+    }
+}
@@ -1,54 +1,198 @@
 use bytes::{Bytes, BytesMut};
 
-use crate::bounded::BoxError;
+use crate::bounded::{BoxError, BoxStream};
 use crate::extract::arg::DefaultArgument;
-use crate::http::{header, Body, Request, Response, ResponseBuilder, StatusCode};
+use crate::extract::decode;
+use crate::http::{header, Request, Response, StatusCode};
+use crate::reject::ErrorResponse;
 use crate::util::_try;
 use crate::Reject;
 
 use std::convert::Infallible;
+use std::error::Error as StdError;
 use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio::time::Sleep;
 
 /// Extract the request body directly into a collection.
 ///
 /// This extractor can be used to read the request body into a `Vec<u8>`, `Bytes`,
 /// or `String`. [`BodyConfig`] can be used to configure the extraction process.
+///
+/// Compressed bodies are decoded as described in [`body_stream`].
 pub async fn body<T>(req: &Request, config: BodyConfig) -> Result<T, BodyRejection>
 where
     T: FromBytes,
 {
+    let mut stream = body_stream(req, config).await?;
+    let mut buf = BytesMut::with_capacity(8192);
+
+    while let Some(chunk) = stream.chunk().await {
+        buf.extend_from_slice(&chunk?);
+    }
+
+    T::from_bytes(buf.freeze()).map_err(|err| BodyRejection(BodyErrorKind::Decode(err.into())))
+}
+
+/// Extract the request body as a stream of chunks.
+///
+/// Unlike [`body`], this extractor does not buffer the request body in memory,
+/// so it is suitable for large uploads that are written directly to disk or
+/// forwarded elsewhere. Chunks are only read from the connection as the stream
+/// is polled. The limit and timeout from [`BodyConfig`] are enforced as data
+/// arrives.
+///
+/// With the `decompression` feature enabled, bodies with a `Content-Encoding`
+/// of `gzip`, `deflate`, `br` or `zstd` are decoded transparently, and the limit
+/// applies to the decoded size. Any other encoding is rejected with a
+/// `415 Unsupported Media Type` response. Without the feature, encoded
+/// bodies are passed through as is.
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::{body_stream, BodyConfig, BodyRejection, BodyStream};
+///
+/// #[derive(Context)]
+/// struct Upload {
+///     #[cx(body_stream = BodyConfig::new().limit(4 << 30))]
+///     body: BodyStream,
+/// }
+///
+/// async fn upload(mut cx: Upload) -> Result<(), BodyRejection> {
+///     while let Some(chunk) = cx.body.chunk().await {
+///         let chunk = chunk?;
+///         // ...
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn body_stream(req: &Request, config: BodyConfig) -> Result<BodyStream, BodyRejection> {
     let body = req
         .body()
         .take()
         .ok_or(BodyRejection(BodyErrorKind::Taken))?;
 
-    if _try! {
-        req.headers()
-            .get(header::CONTENT_LENGTH)?
-            .parse::<usize>()
-            .ok()
-    } > Some(config.limit)
-    {
-        return Err(BodyRejection(BodyErrorKind::Overflow));
+    let body = match req.headers().get(header::CONTENT_ENCODING) {
+        // the limit applies to the decoded body, so the content-length
+        // can only be checked ahead of time for unencoded bodies. without
+        // the decompression feature, encoded bodies are passed through as is
+        Some(encoding)
+            if cfg!(feature = "decompression") && !encoding.eq_ignore_ascii_case("identity") =>
+        {
+            decode::decode(body, &encoding)
+                .map_err(|encoding| BodyRejection(BodyErrorKind::Encoding(encoding)))?
+        }
+        _ => {
+            if _try! {
+                req.headers()
+                    .get(header::CONTENT_LENGTH)?
+                    .parse::<usize>()
+                    .ok()
+            } > Some(config.limit)
+            {
+                return Err(BodyRejection(BodyErrorKind::Overflow));
+            }
+
+            Box::pin(body)
+        }
+    };
+
+    Ok(BodyStream {
+        body,
+        read: 0,
+        limit: config.limit,
+        timeout: config.timeout,
+        sleep: None,
+        done: false,
+    })
+}
+
+/// A stream of chunks of the request body.
+///
+/// See [`body_stream`] for details.
+pub struct BodyStream {
+    body: BoxStream<'static, Result<Bytes, BoxError>>,
+    read: usize,
+    limit: usize,
+    timeout: Option<Duration>,
+    sleep: Option<Pin<Box<Sleep>>>,
+    done: bool,
+}
+
+impl BodyStream {
+    /// Read the next chunk of the body.
+    pub async fn chunk(&mut self) -> Option<Result<Bytes, BodyRejection>> {
+        crate::util::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
     }
+}
 
-    let mut buf = BytesMut::with_capacity(8192);
+impl Stream for BodyStream {
+    type Item = Result<Bytes, BodyRejection>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
 
-    loop {
-        match body.chunk().await {
-            Some(Err(e)) => return Err(BodyRejection(BodyErrorKind::Io(e))),
+        let result = match self.body.as_mut().poll_next(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => {
+                if let Some(timeout) = self.timeout {
+                    let sleep = self
+                        .sleep
+                        .get_or_insert_with(|| Box::pin(tokio::time::sleep(timeout)));
+
+                    if sleep.as_mut().poll(cx).is_ready() {
+                        self.done = true;
+                        return Poll::Ready(Some(Err(BodyRejection(BodyErrorKind::Timeout))));
+                    }
+                }
+
+                return Poll::Pending;
+            }
+        };
+
+        // the timeout applies to each chunk
+        self.sleep = None;
+
+        let result = match result {
             Some(Ok(chunk)) => {
-                if buf.len() + chunk.len() > config.limit {
-                    return Err(BodyRejection(BodyErrorKind::Overflow));
+                self.read += chunk.len();
+                if self.read > self.limit {
+                    Err(BodyRejection(BodyErrorKind::Overflow))
                 } else {
-                    buf.extend_from_slice(&chunk);
+                    Ok(chunk)
                 }
             }
-            None => break,
-        }
+            Some(Err(err)) => Err(BodyRejection(BodyErrorKind::Io(err))),
+            None => {
+                self.done = true;
+                return Poll::Ready(None);
+            }
+        };
+
+        self.done = result.is_err();
+        Poll::Ready(Some(result))
     }
 
-    T::from_bytes(buf.freeze()).map_err(|err| BodyRejection(BodyErrorKind::Decode(err.into())))
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.body.size_hint()
+    }
+}
+
+impl fmt::Debug for BodyStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BodyStream")
+            .field("read", &self.read)
+            .field("limit", &self.limit)
+            .finish()
+    }
 }
 
 /// A type that can be decoded from the raw bytes of the request body.
@@ -87,9 +231,10 @@ impl FromBytes for String {
     }
 }
 
-/// Configuration for the [`body`] extractor.
+/// Configuration for the [`body`] and [`body_stream`] extractors.
 pub struct BodyConfig {
     limit: usize,
+    timeout: Option<Duration>,
 }
 
 impl BodyConfig {
@@ -97,6 +242,7 @@ impl BodyConfig {
     pub fn new() -> Self {
         Self {
             limit: 262_144, // (~256kB)
+            timeout: None,
         }
     }
 
@@ -107,6 +253,14 @@ impl BodyConfig {
         self.limit = limit;
         self
     }
+
+    /// Set the maximum amount of time to wait for each chunk of the body.
+    ///
+    /// By default there is no timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
 }
 
 impl DefaultArgument for BodyConfig {
@@ -123,6 +277,8 @@ pub struct BodyRejection(BodyErrorKind);
 enum BodyErrorKind {
     Taken,
     Overflow,
+    Timeout,
+    Encoding(String),
     Io(BoxError),
     Decode(BoxError),
 }
@@ -132,6 +288,10 @@ impl fmt::Display for BodyRejection {
         match &self.0 {
             BodyErrorKind::Io(err) => write!(f, "failed to read request body: {}", err),
             BodyErrorKind::Overflow => write!(f, "body larger than limit"),
+            BodyErrorKind::Timeout => write!(f, "timed out reading request body"),
+            BodyErrorKind::Encoding(encoding) => {
+                write!(f, "unsupported content-encoding '{}'", encoding)
+            }
             BodyErrorKind::Decode(err) => write!(f, "failed to extract body from request: {}", err),
             BodyErrorKind::Taken => {
                 write!(f, "cannot have two body extractors for a single handler")
@@ -140,17 +300,27 @@ impl fmt::Display for BodyRejection {
     }
 }
 
+impl std::error::Error for BodyRejection {}
+
 impl Reject for BodyRejection {
-    fn reject(self, _: &Request) -> Response {
-        let status = match self.0 {
+    fn status(&self) -> StatusCode {
+        match self.0 {
             BodyErrorKind::Taken => StatusCode::INTERNAL_SERVER_ERROR,
             BodyErrorKind::Overflow => StatusCode::PAYLOAD_TOO_LARGE,
+            BodyErrorKind::Timeout => StatusCode::REQUEST_TIMEOUT,
+            BodyErrorKind::Encoding(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             BodyErrorKind::Io(_) | BodyErrorKind::Decode(_) => StatusCode::BAD_REQUEST,
-        };
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.0 {
+            BodyErrorKind::Io(err) | BodyErrorKind::Decode(err) => Some(&**err),
+            _ => None,
+        }
+    }
 
-        ResponseBuilder::new()
-            .status(status)
-            .body(Body::empty())
-            .unwrap()
+    fn reject(self, req: &Request) -> Response {
+        ErrorResponse::new(self.status(), self.to_string()).into_response(req)
     }
 }
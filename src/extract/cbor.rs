@@ -0,0 +1,123 @@
+use crate::extract::arg::DefaultArgument;
+use crate::extract::{self, BodyConfig, BodyRejection};
+use crate::http::{header, Bytes, Request, StatusCode};
+use crate::reject::ErrorResponse;
+use crate::{Reject, Response};
+
+use serde::de::DeserializeOwned;
+
+use std::error::Error as StdError;
+use std::fmt;
+
+/// Deserialize the given type as CBOR from the request body.
+///
+/// [`CborConfig`] can be used to configure the extraction process.
+pub async fn cbor<T>(req: &Request, config: CborConfig) -> Result<T, CborRejection>
+where
+    T: DeserializeOwned,
+{
+    if !is_cbor(req) {
+        return Err(CborRejection(CborRejectionKind::ContentType));
+    }
+
+    let body = extract::body::<Bytes>(req, BodyConfig::new().limit(config.limit))
+        .await
+        .map_err(|err| CborRejection(CborRejectionKind::Body(err)))?;
+
+    ciborium::de::from_reader(&body[..]).map_err(|err| CborRejection(CborRejectionKind::Deser(err)))
+}
+
+/// Configuration for the [`cbor`] extractor.
+pub struct CborConfig {
+    limit: usize,
+}
+
+impl CborConfig {
+    /// Create a [`CborConfig`] instance.
+    pub fn new() -> Self {
+        Self {
+            limit: 2_097_152, // (~2mb)
+        }
+    }
+
+    /// Set maximum number of bytes that can be streamed.
+    ///
+    /// By default the limit is 2mb.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+impl DefaultArgument for CborConfig {
+    fn new(_: &'static str) -> Self {
+        Self::new()
+    }
+}
+
+fn is_cbor(req: &Request) -> bool {
+    let mime = || {
+        req.headers()
+            .get(header::CONTENT_TYPE)?
+            .parse::<mime::Mime>()
+            .ok()
+    };
+
+    match mime() {
+        Some(mime) => {
+            mime.type_() == mime::APPLICATION
+                && (mime.subtype() == "cbor"
+                    || mime.suffix().is_some_and(|suffix| suffix == "cbor"))
+        }
+        None => false,
+    }
+}
+
+/// The error returned by [`extract::cbor`](cbor()) if extraction fails.
+#[derive(Debug)]
+pub struct CborRejection(CborRejectionKind);
+
+#[derive(Debug)]
+enum CborRejectionKind {
+    ContentType,
+    Body(BodyRejection),
+    Deser(ciborium::de::Error<std::io::Error>),
+}
+
+impl fmt::Display for CborRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            CborRejectionKind::ContentType => write!(f, "expected content-type application/cbor"),
+            CborRejectionKind::Body(err) => write!(f, "failed to read body: {}", err),
+            CborRejectionKind::Deser(err) => write!(f, "failed to deserialize body: {}", err),
+        }
+    }
+}
+
+impl Reject for CborRejection {
+    fn status(&self) -> StatusCode {
+        match &self.0 {
+            CborRejectionKind::Body(err) => err.status(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.0 {
+            CborRejectionKind::ContentType => None,
+            CborRejectionKind::Body(err) => Reject::source(err),
+            CborRejectionKind::Deser(err) => Some(err),
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let error = ErrorResponse::new(StatusCode::BAD_REQUEST, self.to_string());
+
+        let error = match self.0 {
+            CborRejectionKind::Body(err) => return Box::new(err).reject(req),
+            CborRejectionKind::ContentType | CborRejectionKind::Deser(_) => error,
+        };
+
+        error.into_response(req)
+    }
+}
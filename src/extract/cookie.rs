@@ -0,0 +1,125 @@
+use crate::bounded::BoxError;
+use crate::extract::arg::ParamName;
+use crate::http::cookie::{CookieJar, Key, PrivateCookieJar, SignedCookieJar};
+use crate::http::{Request, Response, StatusCode};
+use crate::reject::ErrorResponse;
+use crate::Reject;
+
+use std::convert::Infallible;
+use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Extracts the cookies sent with the request into a [`CookieJar`].
+///
+/// See the [`cookie`](crate::http::cookie) module for details.
+pub async fn cookies(req: &Request, _: ()) -> Result<CookieJar, Infallible> {
+    Ok(CookieJar::from_request(req))
+}
+
+/// Extracts the value of a single cookie from the request.
+///
+/// # Examples
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::{cookie, Optional};
+///
+/// #[derive(Context)]
+/// struct Preferences {
+///     #[cx(cookie = "session")]
+///     session_id: String,
+///     #[cx(cookie)] // #[cx(cookie = "theme")]
+///     theme: Optional<String>,
+/// }
+/// ```
+pub async fn cookie<T>(req: &Request, name: ParamName) -> Result<T, CookieRejection>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    let jar = CookieJar::from_request(req);
+
+    let cookie = jar
+        .get(name.0)
+        .ok_or(CookieRejection(CookieRejectionKind::NotFound(name.0)))?;
+
+    T::from_str(cookie.value())
+        .map_err(|err| CookieRejection(CookieRejectionKind::FromStr(name.0, err.into())))
+}
+
+/// Extracts the cookies sent with the request into a [`SignedCookieJar`].
+///
+/// The signing [`Key`] must be injected with [`Bison::inject`](crate::Bison::inject).
+pub async fn signed_cookies(req: &Request, _: ()) -> Result<SignedCookieJar, CookieRejection> {
+    let key = key(req)?;
+    Ok(CookieJar::from_request(req).signed(key))
+}
+
+/// Extracts the cookies sent with the request into a [`PrivateCookieJar`].
+///
+/// The encryption [`Key`] must be injected with [`Bison::inject`](crate::Bison::inject).
+pub async fn private_cookies(req: &Request, _: ()) -> Result<PrivateCookieJar, CookieRejection> {
+    let key = key(req)?;
+    Ok(CookieJar::from_request(req).private(key))
+}
+
+fn key(req: &Request) -> Result<Key, CookieRejection> {
+    req.state::<Key>()
+        .cloned()
+        .ok_or(CookieRejection(CookieRejectionKind::MissingKey))
+}
+
+/// The error returned by the cookie extractors if extraction fails.
+#[derive(Debug)]
+pub struct CookieRejection(CookieRejectionKind);
+
+#[derive(Debug)]
+enum CookieRejectionKind {
+    NotFound(&'static str),
+    FromStr(&'static str, BoxError),
+    MissingKey,
+}
+
+impl fmt::Display for CookieRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            CookieRejectionKind::NotFound(name) => write!(f, "missing cookie '{}'", name),
+            CookieRejectionKind::FromStr(name, err) => {
+                write!(f, "malformed cookie '{}': {}", name, err)
+            }
+            CookieRejectionKind::MissingKey => write!(f, "no cookie key injected"),
+        }
+    }
+}
+
+impl Reject for CookieRejection {
+    fn status(&self) -> StatusCode {
+        match self.0 {
+            CookieRejectionKind::NotFound(_) | CookieRejectionKind::FromStr(..) => {
+                StatusCode::BAD_REQUEST
+            }
+            CookieRejectionKind::MissingKey => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.0 {
+            CookieRejectionKind::FromStr(_, err) => Some(&**err),
+            _ => None,
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let error = ErrorResponse::new(self.status(), self.to_string());
+
+        let error = match self.0 {
+            CookieRejectionKind::NotFound(name) | CookieRejectionKind::FromStr(name, _) => {
+                error.field(name)
+            }
+            CookieRejectionKind::MissingKey => error,
+        };
+
+        error.into_response(req)
+    }
+}
@@ -0,0 +1,104 @@
+//! Transparent decoding of compressed request bodies.
+
+use crate::bounded::{BoxError, BoxStream};
+use crate::http::Body;
+
+use bytes::Bytes;
+
+/// Wrap the body in decoders for the given `Content-Encoding`.
+///
+/// Encodings are listed in the order they were applied, so they are removed
+/// in reverse. Returns the unsupported encoding on failure.
+pub(crate) fn decode(
+    body: Body,
+    encoding: &str,
+) -> Result<BoxStream<'static, Result<Bytes, BoxError>>, String> {
+    let mut stream: BoxStream<'static, _> = Box::pin(body);
+
+    for encoding in encoding.rsplit(',').map(str::trim) {
+        if encoding.eq_ignore_ascii_case("identity") || encoding.is_empty() {
+            continue;
+        }
+
+        stream = decoder(stream, encoding).ok_or_else(|| encoding.to_owned())?;
+    }
+
+    Ok(stream)
+}
+
+crate::util::cfg_decompression! {
+    use crate::bounded::{Send, Sync};
+
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use async_compression::tokio::bufread::{BrotliDecoder, GzipDecoder, ZlibDecoder, ZstdDecoder};
+    use futures_core::Stream;
+    use tokio::io::AsyncRead;
+    use tokio_util::io::{ReaderStream, StreamReader};
+
+    fn decoder(
+        stream: BoxStream<'static, Result<Bytes, BoxError>>,
+        encoding: &str,
+    ) -> Option<BoxStream<'static, Result<Bytes, BoxError>>> {
+        let reader = StreamReader::new(IntoIo(stream));
+
+        let decoded = match encoding.to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => decoded(GzipDecoder::new(reader)),
+            "deflate" => decoded(ZlibDecoder::new(reader)),
+            "br" => decoded(BrotliDecoder::new(reader)),
+            "zstd" => decoded(ZstdDecoder::new(reader)),
+            _ => return None,
+        };
+
+        Some(decoded)
+    }
+
+    fn decoded<R>(reader: R) -> BoxStream<'static, Result<Bytes, BoxError>>
+    where
+        R: AsyncRead + Unpin + Send + Sync + 'static,
+    {
+        Box::pin(FromIo(ReaderStream::new(reader)))
+    }
+
+    /// Maps the errors of a body stream into `io::Error`s.
+    struct IntoIo(BoxStream<'static, Result<Bytes, BoxError>>);
+
+    impl Stream for IntoIo {
+        type Item = io::Result<Bytes>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.0
+                .as_mut()
+                .poll_next(cx)
+                // body errors are not `Send` with the `not-send` feature
+                .map_err(|err| io::Error::other(err.to_string()))
+        }
+    }
+
+    /// Maps the `io::Error`s of a decoded stream back into body errors.
+    struct FromIo<S>(S);
+
+    impl<S> Stream for FromIo<S>
+    where
+        S: Stream<Item = io::Result<Bytes>> + Unpin,
+    {
+        type Item = Result<Bytes, BoxError>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.0)
+                .poll_next(cx)
+                .map_err(|err| Box::new(err) as _)
+        }
+    }
+}
+
+crate::util::cfg_not_decompression! {
+    fn decoder(
+        _: BoxStream<'static, Result<Bytes, BoxError>>,
+        _: &str,
+    ) -> Option<BoxStream<'static, Result<Bytes, BoxError>>> {
+        None
+    }
+}
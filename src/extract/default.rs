@@ -1,6 +1,7 @@
 use crate::extract::arg::{FieldName, ParamName};
 use crate::extract::{path, query, FromPath, FromQuery};
-use crate::http::{Body, Request, Response, ResponseBuilder, StatusCode};
+use crate::http::{Request, Response, StatusCode};
+use crate::reject::ErrorResponse;
 use crate::Reject;
 
 use std::fmt;
@@ -23,6 +24,7 @@ where
     }
 
     Err(DefaultRejection {
+        field: field_name.as_str(),
         ty: std::any::type_name::<T>(),
     })
 }
@@ -32,24 +34,35 @@ where
 /// Returns a 400 response when used as a rejection.
 #[derive(Debug)]
 pub struct DefaultRejection {
+    field: &'static str,
     ty: &'static str,
 }
 
+impl DefaultRejection {
+    /// Returns the name of the field that could not be extracted.
+    pub fn field(&self) -> &str {
+        self.field
+    }
+}
+
 impl fmt::Display for DefaultRejection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "failed to extract `{}` from route parameter or query string",
-            self.ty
+            "failed to extract `{}` for '{}' from route parameter or query string",
+            self.ty, self.field
         )
     }
 }
 
 impl Reject for DefaultRejection {
-    fn reject(self, _: &Request) -> Response {
-        ResponseBuilder::new()
-            .status(StatusCode::BAD_REQUEST)
-            .body(Body::empty())
-            .unwrap()
+    fn status(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        ErrorResponse::new(self.status(), self.to_string())
+            .field(self.field)
+            .into_response(req)
     }
 }
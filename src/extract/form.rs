@@ -1,10 +1,12 @@
 use crate::extract::arg::DefaultArgument;
 use crate::extract::{self, BodyConfig, BodyRejection};
-use crate::http::{header, Body, Bytes, Request, ResponseBuilder, StatusCode};
+use crate::http::{header, Bytes, Request, StatusCode};
+use crate::reject::ErrorResponse;
 use crate::{Reject, Response};
 
 use serde::de::DeserializeOwned;
 
+use std::error::Error as StdError;
 use std::fmt;
 
 /// Deserialize the given type from a URL encoded form.
@@ -84,15 +86,29 @@ impl fmt::Display for FormRejection {
 }
 
 impl Reject for FormRejection {
+    fn status(&self) -> StatusCode {
+        match &self.0 {
+            FormRejectionKind::Body(err) => err.status(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.0 {
+            FormRejectionKind::ContentType => None,
+            FormRejectionKind::Body(err) => Reject::source(err),
+            FormRejectionKind::Deser(err) => Some(err),
+        }
+    }
+
     fn reject(self, req: &Request) -> Response {
-        let status = match self.0 {
+        let error = ErrorResponse::new(StatusCode::BAD_REQUEST, self.to_string());
+
+        let error = match self.0 {
             FormRejectionKind::Body(err) => return err.reject(req),
-            FormRejectionKind::ContentType | FormRejectionKind::Deser(_) => StatusCode::BAD_REQUEST,
+            FormRejectionKind::ContentType | FormRejectionKind::Deser(_) => error,
         };
 
-        ResponseBuilder::new()
-            .status(status)
-            .body(Body::empty())
-            .unwrap()
+        error.into_response(req)
     }
 }
@@ -0,0 +1,265 @@
+use crate::bounded::BoxError;
+use crate::extract::arg::HeaderName;
+use crate::http::headers::*;
+use crate::http::{Request, Response, StatusCode};
+use crate::reject::ErrorResponse;
+use crate::Reject;
+
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::error::Error as StdError;
+use std::fmt;
+use std::net::*;
+use std::num::*;
+use std::str::FromStr;
+
+/// Extracts a header from the request.
+///
+/// The header name defaults to the name of the field, with underscores
+/// replaced by dashes.
+///
+/// # Examples
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::{header, Optional};
+///
+/// #[derive(Context)]
+/// struct Authenticate {
+///     #[cx(header = "x-api-key")]
+///     key: String,
+///     #[cx(header)] // #[cx(header = "user-agent")]
+///     user_agent: Optional<String>,
+///     #[cx(header = "accept-language")]
+///     languages: Vec<String>,
+/// }
+/// ```
+pub async fn header<T>(req: &Request, name: HeaderName) -> Result<T, HeaderRejection>
+where
+    T: FromHeader,
+{
+    let name = name.0;
+
+    let key = match http::header::HeaderName::from_bytes(name.as_bytes()) {
+        Ok(key) => key,
+        Err(err) => {
+            return Err(HeaderRejection {
+                name,
+                kind: HeaderRejectionKind::InvalidName(err),
+            })
+        }
+    };
+
+    let values = req.headers().get_all(key);
+
+    if values.is_empty() {
+        return Err(HeaderRejection {
+            name,
+            kind: HeaderRejectionKind::NotFound,
+        });
+    }
+
+    let values = values
+        .iter()
+        .map(|value| value.as_str())
+        .collect::<Vec<_>>();
+
+    T::from_header(&values).map_err(|err| HeaderRejection {
+        name,
+        kind: HeaderRejectionKind::FromHeader(err.into()),
+    })
+}
+
+/// Extracts a [typed header](crate::http::headers) from the request.
+///
+/// # Examples
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::{typed_header, Optional};
+/// use bison::http::headers::{Authorization, IfNoneMatch};
+///
+/// #[derive(Context)]
+/// struct Download {
+///     #[cx(typed_header)]
+///     authorization: Authorization,
+///     #[cx(typed_header)]
+///     if_none_match: Optional<IfNoneMatch>,
+/// }
+/// ```
+pub async fn typed_header<T>(req: &Request, _: ()) -> Result<T, HeaderRejection>
+where
+    T: TypedHeader,
+{
+    decode_typed_header(req)?.ok_or_else(|| HeaderRejection {
+        name: Cow::Owned(T::NAME.as_str().to_owned()),
+        kind: HeaderRejectionKind::NotFound,
+    })
+}
+
+/// Decodes a typed header, returning `None` if it is not present.
+pub(crate) fn decode_typed_header<T>(req: &Request) -> Result<Option<T>, HeaderRejection>
+where
+    T: TypedHeader,
+{
+    let values = req.headers().get_all(T::NAME);
+
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let values = values
+        .iter()
+        .map(|value| value.as_str())
+        .collect::<Vec<_>>();
+
+    T::decode(&values).map(Some).map_err(|err| HeaderRejection {
+        name: Cow::Owned(T::NAME.as_str().to_owned()),
+        kind: HeaderRejectionKind::FromHeader(err.into()),
+    })
+}
+
+/// A type that can be extracted from a request header.
+///
+/// Types implementing this trait can be used with the [`header`]
+/// extractor.
+pub trait FromHeader: Sized {
+    /// Errors that can occur in [`from_header`](FromHeader::from_header).
+    type Error: Into<BoxError>;
+
+    /// Extract the type from the values of a header.
+    ///
+    /// `values` holds every value sent for the header, in order, and
+    /// is never empty. Types representing a single value should use
+    /// the first.
+    fn from_header(values: &[&str]) -> Result<Self, Self::Error>;
+}
+
+impl FromHeader for String {
+    type Error = Infallible;
+
+    fn from_header(values: &[&str]) -> Result<Self, Self::Error> {
+        Ok(values[0].to_owned())
+    }
+}
+
+/// Collects all values of a header.
+///
+/// Comma separated lists are split into individual values, so
+/// `accept-language: en, fr` is extracted the same way as two
+/// separate headers.
+impl<T> FromHeader for Vec<T>
+where
+    T: FromHeader,
+{
+    type Error = T::Error;
+
+    fn from_header(values: &[&str]) -> Result<Self, Self::Error> {
+        values
+            .iter()
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| T::from_header(&[value]))
+            .collect()
+    }
+}
+
+macro_rules! from_header {
+    ($($ty:ty),*) => ($(
+        impl FromHeader for $ty {
+            type Error = <$ty as FromStr>::Err;
+
+            fn from_header(values: &[&str]) -> Result<Self, Self::Error> {
+                <$ty as FromStr>::from_str(values[0].trim())
+            }
+        }
+    )*)
+}
+
+from_header! {
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64,
+    bool, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAddr,
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize
+}
+
+macro_rules! from_typed_header {
+    ($($ty:ty),*) => ($(
+        impl FromHeader for $ty {
+            type Error = InvalidHeader;
+
+            fn from_header(values: &[&str]) -> Result<Self, Self::Error> {
+                <$ty as TypedHeader>::decode(values)
+            }
+        }
+    )*)
+}
+
+from_typed_header! {
+    ContentType, Accept, AcceptLanguage, AcceptEncoding, AcceptCharset, Authorization, ETag,
+    IfNoneMatch, Range, CacheControl, UserAgent, Cookie
+}
+
+/// The error returned by [`extract::header`](header()) and
+/// [`extract::typed_header`](typed_header()) if extraction fails.
+///
+/// Returns a 400 response when used as a rejection.
+#[derive(Debug)]
+pub struct HeaderRejection {
+    name: Cow<'static, str>,
+    kind: HeaderRejectionKind,
+}
+
+impl HeaderRejection {
+    /// Returns the name of the header that could not be extracted.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug)]
+enum HeaderRejectionKind {
+    NotFound,
+    FromHeader(BoxError),
+    InvalidName(http::header::InvalidHeaderName),
+}
+
+impl fmt::Display for HeaderRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            HeaderRejectionKind::NotFound => write!(f, "missing header '{}'", self.name),
+            HeaderRejectionKind::FromHeader(err) => {
+                write!(f, "malformed header '{}': {}", self.name, err)
+            }
+            HeaderRejectionKind::InvalidName(err) => {
+                write!(f, "cannot extract header '{}': {}", self.name, err)
+            }
+        }
+    }
+}
+
+impl Reject for HeaderRejection {
+    fn status(&self) -> StatusCode {
+        match self.kind {
+            HeaderRejectionKind::NotFound | HeaderRejectionKind::FromHeader(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            HeaderRejectionKind::InvalidName(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.kind {
+            HeaderRejectionKind::FromHeader(err) => Some(&**err),
+            HeaderRejectionKind::InvalidName(err) => Some(err),
+            HeaderRejectionKind::NotFound => None,
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        ErrorResponse::new(self.status(), self.to_string())
+            .field(self.name())
+            .into_response(req)
+    }
+}
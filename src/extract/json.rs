@@ -1,10 +1,12 @@
 use crate::extract::arg::DefaultArgument;
 use crate::extract::{self, BodyConfig, BodyRejection};
-use crate::http::{header, Body, Bytes, Request, ResponseBuilder, StatusCode};
+use crate::http::{header, Bytes, Request, StatusCode};
+use crate::reject::ErrorResponse;
 use crate::{Reject, Response};
 
 use serde::de::DeserializeOwned;
 
+use std::error::Error as StdError;
 use std::fmt;
 
 /// Deserialize the given type as JSON from the request body.
@@ -88,15 +90,30 @@ impl fmt::Display for JsonRejection {
 }
 
 impl Reject for JsonRejection {
+    fn status(&self) -> StatusCode {
+        match &self.0 {
+            JsonRejectionKind::Body(err) => err.status(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.0 {
+            JsonRejectionKind::ContentType => None,
+            JsonRejectionKind::Body(err) => Reject::source(err),
+            JsonRejectionKind::Deser(err) => Some(err),
+        }
+    }
+
     fn reject(self, req: &Request) -> Response {
-        let status = match self.0 {
+        let error = ErrorResponse::new(StatusCode::BAD_REQUEST, self.to_string());
+
+        let error = match self.0 {
             JsonRejectionKind::Body(err) => return Box::new(err).reject(req),
-            JsonRejectionKind::ContentType | JsonRejectionKind::Deser(_) => StatusCode::BAD_REQUEST,
+            JsonRejectionKind::ContentType => error,
+            JsonRejectionKind::Deser(err) => error.location(err.line(), err.column()),
         };
 
-        ResponseBuilder::new()
-            .status(status)
-            .body(Body::empty())
-            .unwrap()
+        error.into_response(req)
     }
 }
@@ -1,8 +1,12 @@
 //! Extract context from a request.
 
 mod body;
+mod de;
+mod decode;
 mod default;
 mod form;
+mod header;
+mod negotiate;
 mod path;
 mod query;
 mod state;
@@ -10,19 +14,55 @@ mod transform;
 
 pub mod arg;
 
-pub use body::{body, BodyConfig, BodyRejection, FromBytes};
+pub use body::{body, body_stream, BodyConfig, BodyRejection, BodyStream, FromBytes};
 pub use default::{default, DefaultRejection};
 pub use form::{form, FormConfig, FormRejection};
-pub use path::{path, FromPath, PathRejection};
-pub use query::{query, FromQuery, QueryRejection};
+pub(crate) use header::decode_typed_header;
+pub use header::{header, typed_header, FromHeader, HeaderRejection};
+pub use negotiate::{negotiation, Negotiation};
+pub use path::{path, path_all, FromPath, PathRejection};
+pub use query::{query, query_all, FromQuery, QueryRejection};
 pub use state::{state, StateRejection};
 pub use transform::{Optional, Transform};
 
+crate::util::cfg_cookies! {
+    mod cookie;
+    pub use cookie::{cookie, cookies, private_cookies, signed_cookies, CookieRejection};
+}
+
+crate::util::cfg_multipart! {
+    mod multipart;
+    pub use multipart::{
+        multipart, multipart_form, Field, Multipart, MultipartConfig, MultipartForm,
+        MultipartRejection, UploadedFile,
+    };
+}
+
+crate::util::cfg_websocket! {
+    mod websocket;
+    pub use websocket::{websocket, WebSocketRejection};
+}
+
 crate::util::cfg_json! {
     mod json;
     pub use json::{json, JsonRejection, JsonConfig};
 }
 
+crate::util::cfg_msgpack! {
+    mod msgpack;
+    pub use msgpack::{msgpack, MsgPackConfig, MsgPackRejection};
+}
+
+crate::util::cfg_cbor! {
+    mod cbor;
+    pub use cbor::{cbor, CborConfig, CborRejection};
+}
+
+crate::util::cfg_protobuf! {
+    mod protobuf;
+    pub use protobuf::{protobuf, ProtobufConfig, ProtobufRejection};
+}
+
 pub async fn nest<T: crate::Context>(req: &crate::Request, _: ()) -> Result<T, crate::Rejection> {
     <T as crate::Context>::extract(req.clone()).await
 }
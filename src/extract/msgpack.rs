@@ -0,0 +1,125 @@
+use crate::extract::arg::DefaultArgument;
+use crate::extract::{self, BodyConfig, BodyRejection};
+use crate::http::{header, Bytes, Request, StatusCode};
+use crate::reject::ErrorResponse;
+use crate::{Reject, Response};
+
+use serde::de::DeserializeOwned;
+
+use std::error::Error as StdError;
+use std::fmt;
+
+/// Deserialize the given type as MessagePack from the request body.
+///
+/// [`MsgPackConfig`] can be used to configure the extraction process.
+pub async fn msgpack<T>(req: &Request, config: MsgPackConfig) -> Result<T, MsgPackRejection>
+where
+    T: DeserializeOwned,
+{
+    if !is_msgpack(req) {
+        return Err(MsgPackRejection(MsgPackRejectionKind::ContentType));
+    }
+
+    let body = extract::body::<Bytes>(req, BodyConfig::new().limit(config.limit))
+        .await
+        .map_err(|err| MsgPackRejection(MsgPackRejectionKind::Body(err)))?;
+
+    rmp_serde::from_slice(&body).map_err(|err| MsgPackRejection(MsgPackRejectionKind::Deser(err)))
+}
+
+/// Configuration for the [`msgpack`] extractor.
+pub struct MsgPackConfig {
+    limit: usize,
+}
+
+impl MsgPackConfig {
+    /// Create a [`MsgPackConfig`] instance.
+    pub fn new() -> Self {
+        Self {
+            limit: 2_097_152, // (~2mb)
+        }
+    }
+
+    /// Set maximum number of bytes that can be streamed.
+    ///
+    /// By default the limit is 2mb.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+impl DefaultArgument for MsgPackConfig {
+    fn new(_: &'static str) -> Self {
+        Self::new()
+    }
+}
+
+fn is_msgpack(req: &Request) -> bool {
+    let mime = || {
+        req.headers()
+            .get(header::CONTENT_TYPE)?
+            .parse::<mime::Mime>()
+            .ok()
+    };
+
+    match mime() {
+        Some(mime) => {
+            mime.type_() == mime::APPLICATION
+                && (matches!(mime.subtype().as_str(), "msgpack" | "x-msgpack")
+                    || mime.suffix().is_some_and(|suffix| suffix == "msgpack"))
+        }
+        None => false,
+    }
+}
+
+/// The error returned by [`extract::msgpack`](msgpack()) if extraction fails.
+#[derive(Debug)]
+pub struct MsgPackRejection(MsgPackRejectionKind);
+
+#[derive(Debug)]
+enum MsgPackRejectionKind {
+    ContentType,
+    Body(BodyRejection),
+    Deser(rmp_serde::decode::Error),
+}
+
+impl fmt::Display for MsgPackRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            MsgPackRejectionKind::ContentType => {
+                write!(f, "expected content-type application/msgpack")
+            }
+            MsgPackRejectionKind::Body(err) => write!(f, "failed to read body: {}", err),
+            MsgPackRejectionKind::Deser(err) => write!(f, "failed to deserialize body: {}", err),
+        }
+    }
+}
+
+impl Reject for MsgPackRejection {
+    fn status(&self) -> StatusCode {
+        match &self.0 {
+            MsgPackRejectionKind::Body(err) => err.status(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.0 {
+            MsgPackRejectionKind::ContentType => None,
+            MsgPackRejectionKind::Body(err) => Reject::source(err),
+            MsgPackRejectionKind::Deser(err) => Some(err),
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let error = ErrorResponse::new(StatusCode::BAD_REQUEST, self.to_string());
+
+        let error = match self.0 {
+            MsgPackRejectionKind::Body(err) => return Box::new(err).reject(req),
+            MsgPackRejectionKind::ContentType | MsgPackRejectionKind::Deser(_) => error,
+        };
+
+        error.into_response(req)
+    }
+}
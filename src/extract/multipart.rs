@@ -0,0 +1,464 @@
+use crate::extract::arg::DefaultArgument;
+use crate::extract::de::{self, DeError};
+use crate::extract::{self, BodyConfig, BodyRejection};
+use crate::http::{header, Bytes, HeaderMap, Request, Response, StatusCode};
+use crate::reject::ErrorResponse;
+use crate::Reject;
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use mime::Mime;
+use serde::de::DeserializeOwned;
+use tempfile::NamedTempFile;
+use tokio::io::AsyncWriteExt;
+
+/// Extract a `multipart/form-data` request body.
+///
+/// Fields are read one at a time with [`Multipart::next_field`], and
+/// the contents of each field are streamed, so large file uploads are
+/// never buffered in memory. [`MultipartConfig`] can be used to limit
+/// the size of each field and of the body as a whole.
+///
+/// # Examples
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::{multipart, Multipart, MultipartConfig, MultipartRejection};
+///
+/// #[derive(Context)]
+/// struct Upload {
+///     #[cx(multipart = MultipartConfig::new().limit(1 << 30))]
+///     form: Multipart,
+/// }
+///
+/// async fn upload(mut cx: Upload) -> Result<(), MultipartRejection> {
+///     while let Some(mut field) = cx.form.next_field().await? {
+///         println!("{:?}: {:?}", field.name(), field.file_name());
+///
+///         while let Some(chunk) = field.chunk().await? {
+///             // ...
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn multipart(
+    req: &Request,
+    config: MultipartConfig,
+) -> Result<Multipart, MultipartRejection> {
+    let boundary = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| multer::parse_boundary(content_type.as_str()).ok())
+        .ok_or(MultipartRejection(MultipartRejectionKind::ContentType))?;
+
+    // the total limit is enforced by the body stream
+    let body = extract::body_stream(req, config.body)
+        .await
+        .map_err(|err| MultipartRejection(MultipartRejectionKind::Body(err)))?;
+
+    let constraints = multer::Constraints::new()
+        .size_limit(multer::SizeLimit::new().per_field(config.field_limit as u64));
+
+    Ok(Multipart {
+        inner: multer::Multipart::with_constraints(body, boundary, constraints),
+    })
+}
+
+/// Deserialize a `multipart/form-data` request body, storing uploaded
+/// files in temporary files.
+///
+/// Text fields are deserialized into `T` the same way as
+/// [`query_all`](super::query_all), while fields with a filename are
+/// written to temporary files as they arrive. Temporary files are deleted
+/// when the corresponding [`UploadedFile`] is dropped, unless they are
+/// [persisted](UploadedFile::persist).
+///
+/// # Examples
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::{multipart_form, MultipartForm};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Profile {
+///     name: String,
+///     bio: Option<String>,
+/// }
+///
+/// #[derive(Context)]
+/// struct UpdateProfile {
+///     #[cx(multipart_form)]
+///     form: MultipartForm<Profile>,
+/// }
+///
+/// async fn update(cx: UpdateProfile) -> std::io::Result<()> {
+///     if let Some(avatar) = cx.form.file("avatar") {
+///         std::fs::copy(avatar.path(), format!("avatars/{}", cx.form.fields.name))?;
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn multipart_form<T>(
+    req: &Request,
+    config: MultipartConfig,
+) -> Result<MultipartForm<T>, MultipartRejection>
+where
+    T: DeserializeOwned,
+{
+    let mut multipart = multipart(req, config).await?;
+
+    let mut fields = Vec::new();
+    let mut files = Vec::new();
+
+    while let Some(field) = multipart.next_field().await? {
+        let name = field.name().unwrap_or_default().to_owned();
+
+        if field.file_name().is_some() {
+            files.push(UploadedFile::write(name, field).await?);
+        } else {
+            fields.push((name, field.text().await?));
+        }
+    }
+
+    let fields = de::from_pairs(&fields)
+        .map_err(|err| MultipartRejection(MultipartRejectionKind::Deserialize(err)))?;
+
+    Ok(MultipartForm { fields, files })
+}
+
+/// A `multipart/form-data` request body.
+///
+/// See [`multipart`] for details.
+pub struct Multipart {
+    inner: multer::Multipart<'static>,
+}
+
+impl Multipart {
+    /// Returns the next field of the form.
+    ///
+    /// The previous field must be dropped before the next field is read.
+    pub async fn next_field(&mut self) -> Result<Option<Field>, MultipartRejection> {
+        match self.inner.next_field().await {
+            Ok(field) => Ok(field.map(|inner| Field { inner })),
+            Err(err) => Err(multer_error(err)),
+        }
+    }
+}
+
+impl fmt::Debug for Multipart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Multipart").finish()
+    }
+}
+
+/// A single field of a [`Multipart`] form.
+///
+/// The contents of the field can be read with [`chunk`](Self::chunk),
+/// or by using the field as a [`Stream`].
+pub struct Field {
+    inner: multer::Field<'static>,
+}
+
+impl Field {
+    /// Returns the name of the field.
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    /// Returns the filename of the field, if it is a file upload.
+    pub fn file_name(&self) -> Option<&str> {
+        self.inner.file_name()
+    }
+
+    /// Returns the content type of the field.
+    pub fn content_type(&self) -> Option<&Mime> {
+        self.inner.content_type()
+    }
+
+    /// Returns the headers of the field.
+    pub fn headers(&self) -> &HeaderMap {
+        self.inner.headers()
+    }
+
+    /// Read the next chunk of the field.
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>, MultipartRejection> {
+        self.inner.chunk().await.map_err(multer_error)
+    }
+
+    /// Read the entire field into memory.
+    pub async fn bytes(self) -> Result<Bytes, MultipartRejection> {
+        self.inner.bytes().await.map_err(multer_error)
+    }
+
+    /// Read the entire field into memory as text.
+    pub async fn text(self) -> Result<String, MultipartRejection> {
+        self.inner.text().await.map_err(multer_error)
+    }
+}
+
+impl Stream for Field {
+    type Item = Result<Bytes, MultipartRejection>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner)
+            .poll_next(cx)
+            .map(|chunk| chunk.map(|chunk| chunk.map_err(multer_error)))
+    }
+}
+
+impl fmt::Debug for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Field")
+            .field("name", &self.name())
+            .field("file_name", &self.file_name())
+            .field("content_type", &self.content_type())
+            .finish()
+    }
+}
+
+/// A deserialized `multipart/form-data` request body.
+///
+/// See [`multipart_form`] for details.
+#[derive(Debug)]
+pub struct MultipartForm<T> {
+    /// The text fields of the form.
+    pub fields: T,
+    /// The files uploaded with the form, in order.
+    pub files: Vec<UploadedFile>,
+}
+
+impl<T> MultipartForm<T> {
+    /// Returns the first file uploaded under the given field name.
+    pub fn file(&self, name: &str) -> Option<&UploadedFile> {
+        self.files.iter().find(|file| file.name == name)
+    }
+}
+
+/// A file uploaded with a [`MultipartForm`], stored in a temporary file.
+#[derive(Debug)]
+pub struct UploadedFile {
+    name: String,
+    file_name: Option<String>,
+    content_type: Option<Mime>,
+    size: u64,
+    file: NamedTempFile,
+}
+
+impl UploadedFile {
+    async fn write(name: String, mut field: Field) -> Result<Self, MultipartRejection> {
+        let io = |err| MultipartRejection(MultipartRejectionKind::Io(err));
+
+        // creating the file blocks, so it happens off of the request task
+        let (file, writer) = tokio::task::spawn_blocking(|| {
+            let file = NamedTempFile::new()?;
+            let writer = file.reopen()?;
+            Ok((file, writer))
+        })
+        .await
+        .map_err(|err| io(err.into()))?
+        .map_err(io)?;
+
+        let mut writer = tokio::fs::File::from_std(writer);
+        let mut size = 0;
+
+        while let Some(chunk) = field.chunk().await? {
+            size += chunk.len() as u64;
+            writer.write_all(&chunk).await.map_err(io)?;
+        }
+
+        writer.flush().await.map_err(io)?;
+
+        Ok(UploadedFile {
+            name,
+            file_name: field.file_name().map(str::to_owned),
+            content_type: field.content_type().cloned(),
+            size,
+            file,
+        })
+    }
+
+    /// Returns the name of the form field.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the filename sent by the client.
+    ///
+    /// This should not be trusted as a path on the server.
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// Returns the content type sent by the client.
+    pub fn content_type(&self) -> Option<&Mime> {
+        self.content_type.as_ref()
+    }
+
+    /// Returns the size of the file in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the path of the temporary file.
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Move the temporary file to the given path, so that it is
+    /// not deleted when dropped.
+    pub fn persist(self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.file.persist(path)?;
+        Ok(())
+    }
+}
+
+/// Configuration for the [`multipart`] and [`multipart_form`] extractors.
+pub struct MultipartConfig {
+    body: BodyConfig,
+    field_limit: usize,
+}
+
+impl MultipartConfig {
+    /// Create a [`MultipartConfig`] instance.
+    pub fn new() -> Self {
+        Self {
+            body: BodyConfig::new().limit(8_388_608), // (~8MB)
+            field_limit: 2_097_152,                   // (~2MB)
+        }
+    }
+
+    /// Set maximum number of bytes that can be streamed for the entire form.
+    ///
+    /// By default the limit is 8MB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.body = self.body.limit(limit);
+        self
+    }
+
+    /// Set maximum number of bytes that can be streamed for a single field.
+    ///
+    /// By default the limit is 2MB.
+    pub fn field_limit(mut self, limit: usize) -> Self {
+        self.field_limit = limit;
+        self
+    }
+
+    /// Set the maximum amount of time to wait for each chunk of the body.
+    ///
+    /// By default there is no timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.body = self.body.timeout(timeout);
+        self
+    }
+}
+
+impl DefaultArgument for MultipartConfig {
+    fn new(_: &'static str) -> Self {
+        Self::new()
+    }
+}
+
+fn multer_error(err: multer::Error) -> MultipartRejection {
+    match err {
+        // errors from the underlying body stream, which may be wrapped
+        // more than once by multer
+        multer::Error::StreamReadFailed(err) => match err.downcast::<BodyRejection>() {
+            Ok(err) => MultipartRejection(MultipartRejectionKind::Body(*err)),
+            Err(err) => match err.downcast::<multer::Error>() {
+                Ok(err) => multer_error(*err),
+                Err(err) => MultipartRejection(MultipartRejectionKind::Multipart(
+                    multer::Error::StreamReadFailed(err),
+                )),
+            },
+        },
+        err => MultipartRejection(MultipartRejectionKind::Multipart(err)),
+    }
+}
+
+/// The error returned by [`extract::multipart`](multipart) and
+/// [`extract::multipart_form`](multipart_form) if extraction fails.
+#[derive(Debug)]
+pub struct MultipartRejection(MultipartRejectionKind);
+
+#[derive(Debug)]
+enum MultipartRejectionKind {
+    ContentType,
+    Body(BodyRejection),
+    Multipart(multer::Error),
+    Deserialize(DeError),
+    Io(io::Error),
+}
+
+impl fmt::Display for MultipartRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            MultipartRejectionKind::ContentType => {
+                write!(f, "expected content-type multipart/form-data")
+            }
+            MultipartRejectionKind::Body(err) => write!(f, "failed to read body: {}", err),
+            MultipartRejectionKind::Multipart(err) => {
+                write!(f, "failed to parse multipart form: {}", err)
+            }
+            MultipartRejectionKind::Deserialize(err) => {
+                write!(f, "failed to deserialize multipart form: {}", err)
+            }
+            MultipartRejectionKind::Io(err) => write!(f, "failed to store uploaded file: {}", err),
+        }
+    }
+}
+
+impl Reject for MultipartRejection {
+    fn status(&self) -> StatusCode {
+        match &self.0 {
+            MultipartRejectionKind::Body(err) => err.status(),
+            MultipartRejectionKind::Multipart(
+                multer::Error::FieldSizeExceeded { .. } | multer::Error::StreamSizeExceeded { .. },
+            ) => StatusCode::PAYLOAD_TOO_LARGE,
+            MultipartRejectionKind::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            MultipartRejectionKind::ContentType
+            | MultipartRejectionKind::Multipart(_)
+            | MultipartRejectionKind::Deserialize(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.0 {
+            MultipartRejectionKind::ContentType => None,
+            MultipartRejectionKind::Body(err) => Reject::source(err),
+            MultipartRejectionKind::Multipart(err) => Some(err),
+            MultipartRejectionKind::Deserialize(err) => Some(err),
+            MultipartRejectionKind::Io(err) => Some(err),
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let error = ErrorResponse::new(self.status(), self.to_string());
+
+        let field = match self.0 {
+            MultipartRejectionKind::Body(err) => return err.reject(req),
+            MultipartRejectionKind::Multipart(multer::Error::FieldSizeExceeded {
+                field_name,
+                ..
+            }) => field_name,
+            MultipartRejectionKind::Deserialize(err) => err.field().map(str::to_owned),
+            _ => None,
+        };
+
+        match field {
+            Some(field) => error.field(field),
+            None => error,
+        }
+        .into_response(req)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::extract::header::decode_typed_header;
+use crate::extract::HeaderRejection;
+use crate::http::headers::{Accept, AcceptCharset, AcceptEncoding, AcceptLanguage};
+use crate::http::Request;
+
+use mime::Mime;
+
+/// Extracts the content negotiation headers from the request.
+///
+/// `Accept`, `Accept-Language`, `Accept-Encoding` and `Accept-Charset`
+/// are all optional, but a malformed header rejects the request with a
+/// [`HeaderRejection`].
+///
+/// # Examples
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::{negotiation, Negotiation};
+///
+/// #[derive(Context)]
+/// struct Greet {
+///     #[cx(negotiation)]
+///     negotiation: Negotiation,
+/// }
+///
+/// async fn greet(cx: Greet) -> &'static str {
+///     match cx.negotiation.language(&["en", "fr"]) {
+///         Some("fr") => "Bonjour!",
+///         _ => "Hello!",
+///     }
+/// }
+/// ```
+pub async fn negotiation(req: &Request, _: ()) -> Result<Negotiation, HeaderRejection> {
+    Ok(Negotiation {
+        accept: decode_typed_header(req)?,
+        language: decode_typed_header(req)?,
+        encoding: decode_typed_header(req)?,
+        charset: decode_typed_header(req)?,
+    })
+}
+
+/// The content negotiation headers of a request.
+///
+/// Each of the `Accept*` headers is optional. A missing header
+/// means that the client accepts anything, so the first of the
+/// available values is chosen.
+///
+/// See [`negotiation`] for details.
+#[derive(Debug, Clone, Default)]
+pub struct Negotiation {
+    accept: Option<Accept>,
+    language: Option<AcceptLanguage>,
+    encoding: Option<AcceptEncoding>,
+    charset: Option<AcceptCharset>,
+}
+
+impl Negotiation {
+    /// Returns the `Accept` header, if present.
+    pub fn accept(&self) -> Option<&Accept> {
+        self.accept.as_ref()
+    }
+
+    /// Returns the `Accept-Language` header, if present.
+    pub fn accept_language(&self) -> Option<&AcceptLanguage> {
+        self.language.as_ref()
+    }
+
+    /// Returns the `Accept-Encoding` header, if present.
+    pub fn accept_encoding(&self) -> Option<&AcceptEncoding> {
+        self.encoding.as_ref()
+    }
+
+    /// Returns the `Accept-Charset` header, if present.
+    pub fn accept_charset(&self) -> Option<&AcceptCharset> {
+        self.charset.as_ref()
+    }
+
+    /// Returns the most preferred of the available media types.
+    pub fn media_type<'a>(&self, available: &'a [Mime]) -> Option<&'a Mime> {
+        match &self.accept {
+            Some(accept) => accept.preferred(available),
+            None => available.first(),
+        }
+    }
+
+    /// Returns the most preferred of the available languages.
+    pub fn language<'a>(&self, available: &[&'a str]) -> Option<&'a str> {
+        match &self.language {
+            Some(language) => language.preferred(available),
+            None => available.first().copied(),
+        }
+    }
+
+    /// Returns the most preferred of the available content codings.
+    pub fn encoding<'a>(&self, available: &[&'a str]) -> Option<&'a str> {
+        match &self.encoding {
+            Some(encoding) => encoding.preferred(available),
+            None => available.first().copied(),
+        }
+    }
+
+    /// Returns the most preferred of the available charsets.
+    pub fn charset<'a>(&self, available: &[&'a str]) -> Option<&'a str> {
+        match &self.charset {
+            Some(charset) => charset.preferred(available),
+            None => available.first().copied(),
+        }
+    }
+}
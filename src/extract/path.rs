@@ -1,14 +1,19 @@
 use crate::bounded::BoxError;
 use crate::extract::arg::ParamName;
-use crate::http::{Body, Request, Response, ResponseBuilder, StatusCode};
+use crate::extract::de::{self, DeError};
+use crate::http::{Request, Response, StatusCode};
+use crate::reject::ErrorResponse;
 use crate::Reject;
 
 use std::convert::Infallible;
+use std::error::Error as StdError;
 use std::fmt;
 use std::net::*;
 use std::num::*;
 use std::str::FromStr;
 
+use serde::de::DeserializeOwned;
+
 /// Extracts a route parameter from the request path.
 ///
 /// ```
@@ -45,6 +50,77 @@ where
     })
 }
 
+/// Deserializes all route parameters into a type.
+///
+/// Parameters can be deserialized into a struct by name, or into a tuple
+/// in the order they appear in the route.
+///
+/// # Examples
+///
+/// ```
+/// use bison::extract::path_all;
+/// use bison::{Bison, Context};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Repo {
+///     org: String,
+///     repo: String,
+/// }
+///
+/// #[derive(Context)]
+/// struct GetIssue {
+///     #[cx(path_all)]
+///     params: (String, String, u64),
+/// }
+///
+/// #[derive(Context)]
+/// struct GetRepo {
+///     #[cx(path_all)]
+///     repo: Repo,
+/// }
+///
+/// async fn get_issue(cx: GetIssue) -> String {
+///     let (org, repo, n) = cx.params;
+///     format!("{}/{}#{}", org, repo, n)
+/// }
+///
+/// let bison = Bison::new().get("/orgs/:org/repos/:repo/issues/:n", get_issue);
+/// ```
+///
+/// The extractor can also be called directly when implementing
+/// [`Context`](crate::Context) by hand:
+///
+/// ```
+/// use bison::{Context, Rejection, Request};
+/// use bison::bounded::BoxFuture;
+/// use bison::extract;
+///
+/// struct GetIssue {
+///     n: u64,
+/// }
+///
+/// impl Context for GetIssue {
+///     type Future = BoxFuture<'static, Result<Self, Rejection>>;
+///
+///     fn extract(req: Request) -> Self::Future {
+///         Box::pin(async move {
+///             let (_, _, n) = extract::path_all::<(String, String, u64)>(&req, ()).await?;
+///             Ok(GetIssue { n })
+///         })
+///     }
+/// }
+/// ```
+pub async fn path_all<T>(req: &Request, _: ()) -> Result<T, PathRejection>
+where
+    T: DeserializeOwned,
+{
+    de::from_pairs(req.route_params()).map_err(|err| PathRejection {
+        name: err.field().unwrap_or_default().to_owned(),
+        kind: PathRejectionKind::Deserialize(err, std::any::type_name::<T>()),
+    })
+}
+
 /// A type that can be extracted from a URL path segment.
 ///
 /// Types implementing this trait can be used with the [`path`]
@@ -84,16 +160,25 @@ from_path! {
     NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize
 }
 
-/// The error returned by [`extract::path`](path()) if extraction fails.
+/// The error returned by [`extract::path`](path()) and
+/// [`extract::path_all`](path_all()) if extraction fails.
 #[derive(Debug)]
 pub struct PathRejection {
     name: String,
     kind: PathRejectionKind,
 }
 
+impl PathRejection {
+    /// Returns the name of the route parameter that could not be extracted, if known.
+    pub fn field(&self) -> Option<&str> {
+        Some(self.name.as_str()).filter(|name| !name.is_empty())
+    }
+}
+
 #[derive(Debug)]
 enum PathRejectionKind {
     FromPath(BoxError),
+    Deserialize(DeError, &'static str),
     NotFound,
 }
 
@@ -103,21 +188,43 @@ impl fmt::Display for PathRejection {
             PathRejectionKind::FromPath(err) => {
                 write!(f, "error extracting route param '{}': {}", self.name, err)
             }
+            PathRejectionKind::Deserialize(err, ty) => {
+                write!(
+                    f,
+                    "failed to deserialize `{}` from route params: {}",
+                    ty, err
+                )
+            }
             PathRejectionKind::NotFound => write!(f, "route param '{}' not found", self.name),
         }
     }
 }
 
 impl Reject for PathRejection {
-    fn reject(self, _: &Request) -> Response {
-        let status = match self.kind {
-            PathRejectionKind::FromPath(_) => StatusCode::BAD_REQUEST,
+    fn status(&self) -> StatusCode {
+        match self.kind {
+            PathRejectionKind::FromPath(_) | PathRejectionKind::Deserialize(..) => {
+                StatusCode::BAD_REQUEST
+            }
             PathRejectionKind::NotFound => StatusCode::NOT_FOUND,
-        };
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.kind {
+            PathRejectionKind::FromPath(err) => Some(&**err),
+            PathRejectionKind::Deserialize(err, _) => Some(err),
+            PathRejectionKind::NotFound => None,
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let mut error = ErrorResponse::new(self.status(), self.to_string());
+
+        if let Some(field) = self.field() {
+            error = error.field(field);
+        }
 
-        ResponseBuilder::new()
-            .status(status)
-            .body(Body::empty())
-            .unwrap()
+        error.into_response(req)
     }
 }
@@ -0,0 +1,127 @@
+use crate::extract::arg::DefaultArgument;
+use crate::extract::{self, BodyConfig, BodyRejection};
+use crate::http::{header, Bytes, Request, StatusCode};
+use crate::reject::ErrorResponse;
+use crate::{Reject, Response};
+
+use prost::Message;
+
+use std::error::Error as StdError;
+use std::fmt;
+
+/// Decode the given Protobuf message from the request body.
+///
+/// [`ProtobufConfig`] can be used to configure the extraction process.
+pub async fn protobuf<T>(req: &Request, config: ProtobufConfig) -> Result<T, ProtobufRejection>
+where
+    T: Message + Default,
+{
+    if !is_protobuf(req) {
+        return Err(ProtobufRejection(ProtobufRejectionKind::ContentType));
+    }
+
+    let body = extract::body::<Bytes>(req, BodyConfig::new().limit(config.limit))
+        .await
+        .map_err(|err| ProtobufRejection(ProtobufRejectionKind::Body(err)))?;
+
+    T::decode(body).map_err(|err| ProtobufRejection(ProtobufRejectionKind::Decode(err)))
+}
+
+/// Configuration for the [`protobuf`] extractor.
+pub struct ProtobufConfig {
+    limit: usize,
+}
+
+impl ProtobufConfig {
+    /// Create a [`ProtobufConfig`] instance.
+    pub fn new() -> Self {
+        Self {
+            limit: 2_097_152, // (~2mb)
+        }
+    }
+
+    /// Set maximum number of bytes that can be streamed.
+    ///
+    /// By default the limit is 2mb.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+impl DefaultArgument for ProtobufConfig {
+    fn new(_: &'static str) -> Self {
+        Self::new()
+    }
+}
+
+fn is_protobuf(req: &Request) -> bool {
+    let mime = || {
+        req.headers()
+            .get(header::CONTENT_TYPE)?
+            .parse::<mime::Mime>()
+            .ok()
+    };
+
+    match mime() {
+        Some(mime) => {
+            mime.type_() == mime::APPLICATION
+                && matches!(
+                    mime.subtype().as_str(),
+                    "protobuf" | "x-protobuf" | "vnd.google.protobuf"
+                )
+        }
+        None => false,
+    }
+}
+
+/// The error returned by [`extract::protobuf`](protobuf()) if extraction fails.
+#[derive(Debug)]
+pub struct ProtobufRejection(ProtobufRejectionKind);
+
+#[derive(Debug)]
+enum ProtobufRejectionKind {
+    ContentType,
+    Body(BodyRejection),
+    Decode(prost::DecodeError),
+}
+
+impl fmt::Display for ProtobufRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            ProtobufRejectionKind::ContentType => {
+                write!(f, "expected content-type application/protobuf")
+            }
+            ProtobufRejectionKind::Body(err) => write!(f, "failed to read body: {}", err),
+            ProtobufRejectionKind::Decode(err) => write!(f, "failed to decode body: {}", err),
+        }
+    }
+}
+
+impl Reject for ProtobufRejection {
+    fn status(&self) -> StatusCode {
+        match &self.0 {
+            ProtobufRejectionKind::Body(err) => err.status(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.0 {
+            ProtobufRejectionKind::ContentType => None,
+            ProtobufRejectionKind::Body(err) => Reject::source(err),
+            ProtobufRejectionKind::Decode(err) => Some(err),
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let error = ErrorResponse::new(StatusCode::BAD_REQUEST, self.to_string());
+
+        let error = match self.0 {
+            ProtobufRejectionKind::Body(err) => return Box::new(err).reject(req),
+            ProtobufRejectionKind::ContentType | ProtobufRejectionKind::Decode(_) => error,
+        };
+
+        error.into_response(req)
+    }
+}
@@ -1,16 +1,20 @@
 use crate::bounded::BoxError;
 use crate::extract::arg::ParamName;
-use crate::http::{Body, Request, Response, ResponseBuilder, StatusCode};
+use crate::extract::de::{self, DeError};
+use crate::http::{Request, Response, StatusCode};
+use crate::reject::ErrorResponse;
 use crate::Reject;
 
 use std::collections::HashMap;
 use std::convert::Infallible;
+use std::error::Error as StdError;
 use std::fmt;
 use std::net::*;
 use std::num::*;
 use std::str::FromStr;
 
 use once_cell::sync::OnceCell;
+use serde::de::DeserializeOwned;
 
 /// Extracts a query parameter from the request.
 ///
@@ -45,6 +49,46 @@ where
     T::from_query(raw).map_err(|err| error.kind(QueryRejectionKind::FromQuery(err.into())))
 }
 
+/// Deserializes the entire query string into a type.
+///
+/// Unlike [`query`], which extracts a single parameter, this extractor
+/// deserializes all parameters into any type implementing [`Deserialize`].
+/// Missing parameters can be handled with `Option` fields or `#[serde(default)]`,
+/// and repeated parameters, such as `?tag=a&tag=b`, can be collected into a `Vec`.
+///
+/// # Examples
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::query_all;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Filters {
+///     name: Option<String>,
+///     #[serde(default)]
+///     page: usize,
+///     tag: Vec<String>,
+/// }
+///
+/// #[derive(Context)]
+/// struct Search {
+///     #[cx(query_all)]
+///     filters: Filters,
+/// }
+/// ```
+///
+/// [`Deserialize`]: serde::Deserialize
+pub async fn query_all<T>(req: &Request, _: ()) -> Result<T, QueryRejection>
+where
+    T: DeserializeOwned,
+{
+    de::from_pairs(req.query_params().as_slice()).map_err(|err| {
+        QueryRejection::builder("", std::any::type_name::<T>())
+            .kind(QueryRejectionKind::Deserialize(err))
+    })
+}
+
 #[derive(Default)]
 pub(crate) struct CachedQuery(OnceCell<HashMap<String, String>>);
 
@@ -115,10 +159,21 @@ impl QueryRejection {
     }
 }
 
+impl QueryRejection {
+    /// Returns the name of the query parameter that could not be extracted, if known.
+    pub fn field(&self) -> Option<&str> {
+        match &self.kind {
+            QueryRejectionKind::NotFound | QueryRejectionKind::FromQuery(_) => Some(self.name),
+            QueryRejectionKind::Deserialize(err) => err.field(),
+        }
+    }
+}
+
 #[derive(Debug)]
 enum QueryRejectionKind {
     NotFound,
     FromQuery(BoxError),
+    Deserialize(DeError),
 }
 
 impl fmt::Display for QueryRejection {
@@ -130,20 +185,40 @@ impl fmt::Display for QueryRejection {
                 "failed to deserialize `{}` from query parameter: {}",
                 self.ty, error
             ),
+            QueryRejectionKind::Deserialize(error) => write!(
+                f,
+                "failed to deserialize `{}` from query string: {}",
+                self.ty, error
+            ),
         }
     }
 }
 
 impl Reject for QueryRejection {
-    fn reject(self, _: &Request) -> Response {
-        let status = match self.kind {
-            QueryRejectionKind::FromQuery(_) => StatusCode::BAD_REQUEST,
+    fn status(&self) -> StatusCode {
+        match self.kind {
+            QueryRejectionKind::FromQuery(_) | QueryRejectionKind::Deserialize(_) => {
+                StatusCode::BAD_REQUEST
+            }
             QueryRejectionKind::NotFound => StatusCode::NOT_FOUND,
-        };
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.kind {
+            QueryRejectionKind::FromQuery(err) => Some(&**err),
+            QueryRejectionKind::Deserialize(err) => Some(err),
+            QueryRejectionKind::NotFound => None,
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let mut error = ErrorResponse::new(self.status(), self.to_string());
+
+        if let Some(field) = self.field() {
+            error = error.field(field);
+        }
 
-        ResponseBuilder::new()
-            .status(status)
-            .body(Body::empty())
-            .unwrap()
+        error.into_response(req)
     }
 }
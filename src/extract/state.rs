@@ -1,4 +1,5 @@
-use crate::http::{Body, Request, Response, ResponseBuilder, StatusCode};
+use crate::http::{Request, Response, StatusCode};
+use crate::reject::ErrorResponse;
 use crate::state::State;
 use crate::Reject;
 
@@ -31,10 +32,11 @@ impl fmt::Display for StateRejection {
 }
 
 impl Reject for StateRejection {
-    fn reject(self, _: &Request) -> Response {
-        ResponseBuilder::new()
-            .status(StatusCode::INTERNAL_SERVER_ERROR)
-            .body(Body::empty())
-            .unwrap()
+    fn status(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response(req)
     }
 }
@@ -0,0 +1,141 @@
+use crate::http::websocket::WebSocketUpgrade;
+use crate::http::{header, HeaderValue, Method, Request, Response, StatusCode};
+use crate::reject::ErrorResponse;
+use crate::Reject;
+
+use std::fmt;
+
+/// Extracts a WebSocket handshake from the request.
+///
+/// The request must be a `GET` request asking to upgrade to the
+/// `websocket` protocol, with a `Sec-WebSocket-Key` and a
+/// `Sec-WebSocket-Version` of 13. The server must also support
+/// upgrades. Otherwise, the request is rejected with a
+/// [`WebSocketRejection`].
+///
+/// See the [`websocket`](crate::http::websocket) module for details.
+pub async fn websocket(req: &Request, _: ()) -> Result<WebSocketUpgrade, WebSocketRejection> {
+    if req.method() != Method::GET {
+        return Err(WebSocketRejection(WebSocketRejectionKind::MethodNotGet));
+    }
+
+    if !has_token(req, header::CONNECTION, "upgrade")
+        || !has_token(req, header::UPGRADE, "websocket")
+    {
+        return Err(WebSocketRejection(WebSocketRejectionKind::NotUpgrade));
+    }
+
+    match req.headers().get(header::SEC_WEBSOCKET_VERSION) {
+        Some(version) if version.trim() == "13" => {}
+        _ => {
+            return Err(WebSocketRejection(
+                WebSocketRejectionKind::UnsupportedVersion,
+            ))
+        }
+    }
+
+    let key = match req.headers().get(header::SEC_WEBSOCKET_KEY) {
+        Some(key) => key.trim().to_owned(),
+        None => return Err(WebSocketRejection(WebSocketRejectionKind::MissingKey)),
+    };
+
+    let requested = req
+        .headers()
+        .get_all(header::SEC_WEBSOCKET_PROTOCOL)
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(|protocol| protocol.trim().to_owned())
+        .collect();
+
+    let on_upgrade = match req.upgrade() {
+        Some(on_upgrade) => on_upgrade,
+        None => return Err(WebSocketRejection(WebSocketRejectionKind::NotUpgradable)),
+    };
+
+    Ok(WebSocketUpgrade::new(key, requested, on_upgrade))
+}
+
+/// Whether the comma-separated header contains the token, ignoring case.
+fn has_token(req: &Request, name: header::HeaderName, token: &str) -> bool {
+    req.headers().get_all(name).iter().any(|value| {
+        value
+            .split(',')
+            .any(|value| value.trim().eq_ignore_ascii_case(token))
+    })
+}
+
+/// The error returned by [`extract::websocket`](websocket()).
+///
+/// Returns a 405 response for requests that are not `GET` requests, a
+/// 426 response if the request did not ask for a supported version of
+/// the protocol or the connection cannot be upgraded, and a 400
+/// response if the `Sec-WebSocket-Key` header is missing.
+#[derive(Debug)]
+pub struct WebSocketRejection(WebSocketRejectionKind);
+
+#[derive(Debug)]
+enum WebSocketRejectionKind {
+    MethodNotGet,
+    NotUpgrade,
+    UnsupportedVersion,
+    MissingKey,
+    NotUpgradable,
+}
+
+impl fmt::Display for WebSocketRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            WebSocketRejectionKind::MethodNotGet => {
+                write!(f, "websocket requests must use the GET method")
+            }
+            WebSocketRejectionKind::NotUpgrade => {
+                write!(f, "request did not ask to upgrade to websocket")
+            }
+            WebSocketRejectionKind::UnsupportedVersion => {
+                write!(f, "unsupported or missing 'sec-websocket-version'")
+            }
+            WebSocketRejectionKind::MissingKey => {
+                write!(f, "header 'sec-websocket-key' not found")
+            }
+            WebSocketRejectionKind::NotUpgradable => {
+                write!(f, "connection cannot be upgraded")
+            }
+        }
+    }
+}
+
+impl Reject for WebSocketRejection {
+    fn status(&self) -> StatusCode {
+        match self.0 {
+            WebSocketRejectionKind::MethodNotGet => StatusCode::METHOD_NOT_ALLOWED,
+            WebSocketRejectionKind::MissingKey => StatusCode::BAD_REQUEST,
+            WebSocketRejectionKind::NotUpgrade
+            | WebSocketRejectionKind::UnsupportedVersion
+            | WebSocketRejectionKind::NotUpgradable => StatusCode::UPGRADE_REQUIRED,
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let mut response = ErrorResponse::new(self.status(), self.to_string()).into_response(req);
+        let headers = response.headers_mut();
+
+        match self.0 {
+            WebSocketRejectionKind::MethodNotGet => {
+                headers.insert(header::ALLOW, HeaderValue::from_static("GET"));
+            }
+            WebSocketRejectionKind::NotUpgrade => {
+                headers.insert(header::UPGRADE, HeaderValue::from_static("websocket"));
+                headers.insert(header::CONNECTION, HeaderValue::from_static("upgrade"));
+            }
+            WebSocketRejectionKind::UnsupportedVersion => {
+                headers.insert(
+                    header::SEC_WEBSOCKET_VERSION,
+                    HeaderValue::from_static("13"),
+                );
+            }
+            _ => {}
+        }
+
+        response
+    }
+}
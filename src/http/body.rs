@@ -1,17 +1,25 @@
-use crate::bounded::{BoxError, BoxStream, RefCell, Send, Sync};
+use crate::bounded::{BoxError, BoxFuture, BoxStream, Lock, Rc, Send, Sync};
+use crate::http::{Request, Response};
+use crate::reject::ErrorResponse;
+use crate::Reject;
 
 use std::error::Error as StdError;
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::{fmt, mem};
 
 pub use bytes::Bytes;
-pub use http::{header, Extensions, HeaderValue, Method, StatusCode};
+use bytes::BytesMut;
+pub use http::{header, Extensions, HeaderMap, HeaderValue, Method, StatusCode};
 
 use futures_core::Stream;
 
 /// Respresents the body of an HTTP message.
-pub struct Body(RefCell<BodyKind>);
+pub struct Body {
+    kind: Lock<BodyKind>,
+    trailers: Rc<Lock<Trailers>>,
+}
 
 enum BodyKind {
     Stream(BoxStream<'static, Result<Bytes, BoxError>>),
@@ -20,7 +28,22 @@ enum BodyKind {
     Taken,
 }
 
+enum Trailers {
+    // the body has not been read to completion yet
+    Unread(BoxFuture<'static, Result<Option<HeaderMap>, BoxError>>),
+    Pending(BoxFuture<'static, Result<Option<HeaderMap>, BoxError>>),
+    Ready(Option<HeaderMap>),
+    Failed(String),
+}
+
 impl Body {
+    fn new(kind: BodyKind) -> Self {
+        Body {
+            kind: Lock::new(kind),
+            trailers: Rc::new(Lock::new(Trailers::Ready(None))),
+        }
+    }
+
     /// Create a `Body` from a stream of bytes.
     pub fn stream<S, E>(stream: S) -> Self
     where
@@ -41,36 +64,67 @@ impl Body {
                     .poll_next(cx)
                     .map_err(|err| Box::new(err) as _)
             }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
         }
 
-        Body(RefCell::new(BodyKind::Stream(Box::pin(MapErr(stream)))))
+        Body::new(BodyKind::Stream(Box::pin(MapErr(stream))))
+    }
+
+    /// Create a `Body` from a stream of bytes, followed by trailers.
+    ///
+    /// The trailers future is polled once the stream has been exhausted.
+    pub fn stream_with_trailers<S, F, E>(stream: S, trailers: F) -> Self
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + Sync + 'static,
+        F: Future<Output = Result<Option<HeaderMap>, E>> + Send + 'static,
+        E: StdError + Send + Sync + 'static,
+    {
+        let trailers = async move { trailers.await.map_err(|err| Box::new(err) as _) };
+
+        Body {
+            trailers: Rc::new(Lock::new(Trailers::Unread(Box::pin(trailers)))),
+            ..Body::stream(stream)
+        }
     }
 
     pub fn try_clone(&self) -> Option<Body> {
-        let kind = match *self.0.borrow_mut() {
+        if let Trailers::Unread(_) | Trailers::Pending(_) = *self.trailers.lock() {
+            return None;
+        }
+
+        let kind = match *self.kind.lock() {
             BodyKind::Stream(_) => return None,
             BodyKind::Once(ref b) => BodyKind::Once(b.clone()),
             BodyKind::Empty => BodyKind::Empty,
             BodyKind::Taken => BodyKind::Taken,
         };
 
-        Some(Body(RefCell::new(kind)))
+        Some(Body {
+            kind: Lock::new(kind),
+            trailers: self.trailers.clone(),
+        })
     }
 
     /// Create a body directly from bytes.
     pub fn once(bytes: impl Into<Bytes>) -> Self {
-        Body(RefCell::new(BodyKind::Once(bytes.into())))
+        Body::new(BodyKind::Once(bytes.into()))
     }
 
     /// Create an empty `Body`.
     pub fn empty() -> Self {
-        Body(RefCell::new(BodyKind::Empty))
+        Body::new(BodyKind::Empty)
     }
 
     pub fn take(&self) -> Option<Body> {
-        match mem::replace(&mut *self.0.borrow_mut(), BodyKind::Taken) {
+        match mem::replace(&mut *self.kind.lock(), BodyKind::Taken) {
             BodyKind::Taken => None,
-            b => Some(Body(RefCell::new(b))),
+            kind => Some(Body {
+                kind: Lock::new(kind),
+                trailers: self.trailers.clone(),
+            }),
         }
     }
 
@@ -78,16 +132,128 @@ impl Body {
         let mut this = self;
         crate::util::poll_fn(|cx| Pin::new(&mut this).poll_next(cx)).await
     }
+
+    /// Returns the trailers sent after the body.
+    ///
+    /// Trailers are only available once the body has been read to completion,
+    /// and an error is returned if it has not been. Reading the trailers
+    /// again after an error returns an error as well.
+    pub async fn trailers(&self) -> Result<Option<HeaderMap>, BoxError> {
+        crate::util::poll_fn(|cx| self.poll_trailers(cx)).await
+    }
+
+    /// Attempt to resolve the trailers sent after the body.
+    ///
+    /// See [`trailers`](Self::trailers) for details.
+    pub fn poll_trailers(&self, cx: &mut Context<'_>) -> Poll<Result<Option<HeaderMap>, BoxError>> {
+        let mut trailers = self.trailers.lock();
+
+        match &mut *trailers {
+            // the trailers cannot arrive until the body has been read
+            Trailers::Unread(_) => Poll::Ready(Err("body has not been read to completion".into())),
+            Trailers::Pending(fut) => match fut.as_mut().poll(cx) {
+                Poll::Ready(Ok(value)) => {
+                    *trailers = Trailers::Ready(value.clone());
+                    Poll::Ready(Ok(value))
+                }
+                Poll::Ready(Err(err)) => {
+                    *trailers = Trailers::Failed(err.to_string());
+                    Poll::Ready(Err(err))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+            Trailers::Ready(value) => Poll::Ready(Ok(value.clone())),
+            Trailers::Failed(err) => Poll::Ready(Err(err.clone().into())),
+        }
+    }
+
+    /// Allow the trailers to be read, once the end of the body has been reached.
+    fn end_of_data(&self) {
+        let mut trailers = self.trailers.lock();
+
+        *trailers = match mem::replace(&mut *trailers, Trailers::Ready(None)) {
+            Trailers::Unread(fut) => Trailers::Pending(fut),
+            trailers => trailers,
+        };
+    }
+
+    /// Buffer the entire body into memory.
+    ///
+    /// The underlying stream is collected at most once and kept as a single
+    /// chunk, so the body can still be read by extractors afterwards, and
+    /// [`try_clone`](Self::try_clone) will succeed. This allows middleware to
+    /// inspect the body before it reaches the handler.
+    ///
+    /// Returns an error if the body is larger than `limit` bytes. If the
+    /// error occurs while reading the stream the body is left consumed.
+    pub async fn buffer(&self, limit: usize) -> Result<Bytes, BufferError> {
+        let kind = mem::replace(&mut *self.kind.lock(), BodyKind::Taken);
+
+        let mut stream = match kind {
+            BodyKind::Stream(stream) if stream.size_hint().0 > limit => {
+                *self.kind.lock() = BodyKind::Stream(stream);
+                return Err(BufferError(BufferErrorKind::Overflow(limit)));
+            }
+            BodyKind::Stream(stream) => stream,
+            BodyKind::Once(bytes) => {
+                let overflow = bytes.len() > limit;
+                *self.kind.lock() = BodyKind::Once(bytes.clone());
+
+                if overflow {
+                    return Err(BufferError(BufferErrorKind::Overflow(limit)));
+                }
+
+                return Ok(bytes);
+            }
+            BodyKind::Empty => {
+                *self.kind.lock() = BodyKind::Empty;
+                return Ok(Bytes::new());
+            }
+            BodyKind::Taken => return Err(BufferError(BufferErrorKind::Taken)),
+        };
+
+        let mut buf = BytesMut::with_capacity(8192);
+
+        loop {
+            match crate::util::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+                Some(Err(err)) => return Err(BufferError(BufferErrorKind::Io(err))),
+                Some(Ok(chunk)) => {
+                    if buf.len() + chunk.len() > limit {
+                        return Err(BufferError(BufferErrorKind::Overflow(limit)));
+                    }
+
+                    buf.extend_from_slice(&chunk);
+                }
+                None => break,
+            }
+        }
+
+        // resolve the trailers as well so that the body can be cloned
+        self.end_of_data();
+        if let Err(err) = self.trailers().await {
+            return Err(BufferError(BufferErrorKind::Io(err)));
+        }
+
+        let bytes = buf.freeze();
+        *self.kind.lock() = BodyKind::Once(bytes.clone());
+        Ok(bytes)
+    }
 }
 
 impl Stream for &Body {
     type Item = Result<Bytes, BoxError>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut inner = self.0.borrow_mut();
+        let mut inner = self.kind.lock();
 
         match &mut *inner {
-            BodyKind::Stream(stream) => stream.as_mut().poll_next(cx),
+            BodyKind::Stream(stream) => {
+                let next = stream.as_mut().poll_next(cx);
+                if let Poll::Ready(None) = next {
+                    self.end_of_data();
+                }
+                next
+            }
             BodyKind::Once(bytes) => {
                 let bytes = mem::take(bytes);
                 *inner = BodyKind::Empty;
@@ -98,7 +264,7 @@ impl Stream for &Body {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        match &*self.0.borrow_mut() {
+        match &*self.kind.lock() {
             BodyKind::Stream(stream) => stream.size_hint(),
             BodyKind::Once(bytes) => (bytes.len(), Some(bytes.len())),
             BodyKind::Empty | BodyKind::Taken => (0, Some(0)),
@@ -118,6 +284,59 @@ impl Stream for Body {
     }
 }
 
+/// The error returned by [`Body::buffer`].
+#[derive(Debug)]
+pub struct BufferError(BufferErrorKind);
+
+#[derive(Debug)]
+enum BufferErrorKind {
+    Taken,
+    Overflow(usize),
+    Io(BoxError),
+}
+
+impl BufferError {
+    /// Returns `true` if the body was larger than the limit.
+    pub fn is_overflow(&self) -> bool {
+        matches!(self.0, BufferErrorKind::Overflow(_))
+    }
+}
+
+impl fmt::Display for BufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            BufferErrorKind::Taken => write!(f, "body has already been taken"),
+            BufferErrorKind::Overflow(limit) => {
+                write!(f, "body larger than limit of {} bytes", limit)
+            }
+            BufferErrorKind::Io(err) => write!(f, "failed to read body: {}", err),
+        }
+    }
+}
+
+impl StdError for BufferError {}
+
+impl Reject for BufferError {
+    fn status(&self) -> StatusCode {
+        match self.0 {
+            BufferErrorKind::Taken => StatusCode::INTERNAL_SERVER_ERROR,
+            BufferErrorKind::Overflow(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            BufferErrorKind::Io(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.0 {
+            BufferErrorKind::Io(err) => Some(&**err),
+            _ => None,
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        ErrorResponse::new(self.status(), self.to_string()).into_response(req)
+    }
+}
+
 impl Default for Body {
     fn default() -> Self {
         Self::empty()
@@ -0,0 +1,207 @@
+//! HTTP cookies.
+//!
+//! Request cookies can be extracted into a [`CookieJar`] with the
+//! [`cookies`](crate::extract::cookies) extractor. Cookies that are added
+//! to or removed from the jar are sent back to the client as `Set-Cookie`
+//! headers when the jar is returned alongside a responder:
+//!
+//! ```
+//! use bison::Context;
+//! use bison::extract::cookies;
+//! use bison::http::cookie::{Cookie, CookieJar, SameSite};
+//!
+//! #[derive(Context)]
+//! struct Login {
+//!     #[cx(cookies)]
+//!     jar: CookieJar,
+//! }
+//!
+//! async fn login(cx: Login) -> (CookieJar, &'static str) {
+//!     let session = Cookie::build(("session", "abc123"))
+//!         .http_only(true)
+//!         .secure(true)
+//!         .same_site(SameSite::Lax);
+//!
+//!     (cx.jar.set(session), "logged in")
+//! }
+//!
+//! async fn logout(cx: Login) -> (CookieJar, &'static str) {
+//!     (cx.jar.remove("session"), "logged out")
+//! }
+//! ```
+//!
+//! [`SignedCookieJar`] and [`PrivateCookieJar`] additionally sign or encrypt
+//! their cookies with a [`Key`] that is injected as application state.
+
+use crate::http::header::{self, HeaderValue};
+use crate::http::headers::InvalidHeader;
+use crate::http::{Request, Response};
+use crate::reject::Rejection;
+use crate::respond::EncodeError;
+use crate::Respond;
+
+pub use cookie::{time, Cookie, CookieBuilder, Expiration, Key, SameSite};
+
+/// A jar of cookies sent with a request.
+///
+/// See the [module level documentation](self) for details.
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar {
+    jar: cookie::CookieJar,
+}
+
+impl CookieJar {
+    /// Create an empty cookie jar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn from_request(req: &Request) -> Self {
+        let mut jar = cookie::CookieJar::new();
+
+        for value in req.headers().get_all(header::COOKIE) {
+            for cookie in Cookie::split_parse_encoded(value.as_str()).flatten() {
+                jar.add_original(cookie.into_owned());
+            }
+        }
+
+        CookieJar { jar }
+    }
+
+    /// Returns the cookie with the given name.
+    pub fn get(&self, name: &str) -> Option<&Cookie<'static>> {
+        self.jar.get(name)
+    }
+
+    /// Add a cookie to the jar, to be set on the client.
+    pub fn set(mut self, cookie: impl Into<Cookie<'static>>) -> Self {
+        self.jar.add(cookie);
+        self
+    }
+
+    /// Remove a cookie from the jar, expiring it on the client.
+    ///
+    /// The path and domain of the cookie must match the ones it
+    /// was set with for the client to remove it.
+    pub fn remove(mut self, cookie: impl Into<Cookie<'static>>) -> Self {
+        self.jar.remove(cookie);
+        self
+    }
+
+    /// Returns an iterator over all cookies in the jar.
+    pub fn iter(&self) -> impl Iterator<Item = &Cookie<'static>> {
+        self.jar.iter()
+    }
+
+    /// Sign cookies with the given key.
+    pub fn signed(self, key: Key) -> SignedCookieJar {
+        SignedCookieJar { jar: self.jar, key }
+    }
+
+    /// Encrypt cookies with the given key.
+    pub fn private(self, key: Key) -> PrivateCookieJar {
+        PrivateCookieJar { jar: self.jar, key }
+    }
+}
+
+/// A jar of signed cookies.
+///
+/// Cookies are signed when added, and only cookies with a valid signature
+/// are returned by [`get`](Self::get), so their values can be read by the
+/// client but not tampered with.
+///
+/// The [`signed_cookies`](crate::extract::signed_cookies) extractor uses
+/// the [`Key`] injected with [`Bison::inject`](crate::Bison::inject).
+#[derive(Clone)]
+pub struct SignedCookieJar {
+    jar: cookie::CookieJar,
+    key: Key,
+}
+
+impl SignedCookieJar {
+    /// Returns the cookie with the given name if its signature is valid.
+    ///
+    /// The returned cookie has its signature stripped.
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.jar.signed(&self.key).get(name)
+    }
+
+    /// Sign and add a cookie to the jar, to be set on the client.
+    pub fn set(mut self, cookie: impl Into<Cookie<'static>>) -> Self {
+        self.jar.signed_mut(&self.key).add(cookie);
+        self
+    }
+
+    /// Remove a cookie from the jar, expiring it on the client.
+    pub fn remove(mut self, cookie: impl Into<Cookie<'static>>) -> Self {
+        self.jar.signed_mut(&self.key).remove(cookie);
+        self
+    }
+}
+
+/// A jar of private cookies.
+///
+/// Cookies are encrypted when added, and only cookies that can be
+/// decrypted are returned by [`get`](Self::get), so their values can
+/// neither be read nor tampered with by the client.
+///
+/// The [`private_cookies`](crate::extract::private_cookies) extractor
+/// uses the [`Key`] injected with [`Bison::inject`](crate::Bison::inject).
+#[derive(Clone)]
+pub struct PrivateCookieJar {
+    jar: cookie::CookieJar,
+    key: Key,
+}
+
+impl PrivateCookieJar {
+    /// Returns the cookie with the given name if it can be decrypted.
+    ///
+    /// The returned cookie has its value decrypted.
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.jar.private(&self.key).get(name)
+    }
+
+    /// Encrypt and add a cookie to the jar, to be set on the client.
+    pub fn set(mut self, cookie: impl Into<Cookie<'static>>) -> Self {
+        self.jar.private_mut(&self.key).add(cookie);
+        self
+    }
+
+    /// Remove a cookie from the jar, expiring it on the client.
+    pub fn remove(mut self, cookie: impl Into<Cookie<'static>>) -> Self {
+        self.jar.private_mut(&self.key).remove(cookie);
+        self
+    }
+}
+
+macro_rules! respond_with_jar {
+    ($($jar:ty),*) => ($(
+        /// Adds a `Set-Cookie` header to the response for every
+        /// cookie that was added to or removed from the jar.
+        impl<T> Respond for ($jar, T)
+        where
+            T: Respond,
+        {
+            type Rejection = Rejection;
+
+            fn respond(self) -> Result<Response, Rejection> {
+                let mut response = self.1.respond().map_err(Rejection::new)?;
+
+                for cookie in self.0.jar.delta() {
+                    let value = HeaderValue::try_from(cookie.encoded().to_string()).map_err(|_| {
+                        EncodeError {
+                            name: header::SET_COOKIE,
+                            err: InvalidHeader("invalid characters in cookie"),
+                        }
+                    })?;
+
+                    response.headers_mut().append(header::SET_COOKIE, value);
+                }
+
+                Ok(response)
+            }
+        }
+    )*)
+}
+
+respond_with_jar!(CookieJar, SignedCookieJar, PrivateCookieJar);
@@ -0,0 +1,986 @@
+//! Typed representations of common HTTP headers.
+//!
+//! Typed headers can be extracted from a request with the
+//! [`typed_header`](crate::extract::typed_header) extractor, and added
+//! to a response by returning them alongside a responder:
+//!
+//! ```
+//! use bison::Context;
+//! use bison::extract::typed_header;
+//! use bison::http::headers::{Accept, CacheControl, ContentType};
+//!
+//! #[derive(Context)]
+//! struct Page {
+//!     #[cx(typed_header)]
+//!     accept: Accept,
+//! }
+//!
+//! async fn page(cx: Page) -> (CacheControl, (ContentType, String)) {
+//!     let cache = CacheControl {
+//!         public: true,
+//!         max_age: Some(std::time::Duration::from_secs(60)),
+//!         ..CacheControl::default()
+//!     };
+//!
+//!     if cx.accept.accepts(ContentType::html().mime()) {
+//!         (cache, (ContentType::html(), "<h1>Hello</h1>".into()))
+//!     } else {
+//!         (cache, (ContentType::text(), "Hello".into()))
+//!     }
+//! }
+//! ```
+
+use crate::http::header::{self, HeaderName};
+use crate::http::HeaderValue;
+
+use std::fmt;
+use std::ops;
+use std::time::Duration;
+
+use mime::Mime;
+
+/// A header with a typed representation.
+pub trait TypedHeader: Sized {
+    /// The name of the header.
+    const NAME: HeaderName;
+
+    /// Decode the header from its values.
+    ///
+    /// `values` holds every value sent for the header, in order,
+    /// and is never empty.
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader>;
+
+    /// Encode the header into a value.
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader>;
+}
+
+/// An error that occured while decoding or encoding a typed header.
+#[derive(Debug)]
+pub struct InvalidHeader(pub(crate) &'static str);
+
+impl fmt::Display for InvalidHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidHeader {}
+
+fn encode(value: String) -> Result<HeaderValue, InvalidHeader> {
+    HeaderValue::try_from(value).map_err(|_| InvalidHeader("invalid characters in header value"))
+}
+
+/// Splits a list of header values by commas.
+fn split<'a>(values: &'a [&'a str]) -> impl Iterator<Item = &'a str> {
+    values
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// A value with a relative quality, as used by `Accept` and related headers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityItem<T> {
+    /// The value.
+    pub value: T,
+    /// The quality of the value, between `0.0` and `1.0`.
+    pub quality: f32,
+}
+
+/// Parses a list of values with optional `q` parameters, sorted by
+/// quality with the most preferred values first.
+pub(crate) fn parse_quality_list<T>(
+    values: &[&str],
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Vec<QualityItem<T>>, InvalidHeader> {
+    let mut items = Vec::new();
+
+    for item in split(values) {
+        let mut value = item;
+        let mut quality = 1.0;
+        let mut offset = 0;
+
+        // the `q` parameter separates media type parameters from extensions
+        for param in item.split(';') {
+            let trimmed = param.trim_start();
+
+            if offset > 0 && (trimmed.starts_with("q=") || trimmed.starts_with("Q=")) {
+                value = &item[..offset - 1];
+                quality = trimmed[2..]
+                    .trim()
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|q| (0.0..=1.0).contains(q))
+                    .ok_or(InvalidHeader("invalid quality value"))?;
+                break;
+            }
+
+            offset += param.len() + 1;
+        }
+
+        let value = parse(value.trim()).ok_or(InvalidHeader("invalid value in list"))?;
+        items.push(QualityItem { value, quality });
+    }
+
+    // stable, so equally preferred values keep their order
+    items.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+    Ok(items)
+}
+
+fn encode_quality(value: &str, quality: f32) -> String {
+    if quality < 1.0 {
+        format!("{};q={}", value, (quality * 1000.0).round() / 1000.0)
+    } else {
+        value.to_owned()
+    }
+}
+
+/// The `Content-Type` header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentType(Mime);
+
+impl ContentType {
+    /// `application/json`
+    pub fn json() -> Self {
+        ContentType(mime::APPLICATION_JSON)
+    }
+
+    /// `text/html; charset=utf-8`
+    pub fn html() -> Self {
+        ContentType(mime::TEXT_HTML_UTF_8)
+    }
+
+    /// `text/plain; charset=utf-8`
+    pub fn text() -> Self {
+        ContentType(mime::TEXT_PLAIN_UTF_8)
+    }
+
+    /// `application/x-www-form-urlencoded`
+    pub fn form_url_encoded() -> Self {
+        ContentType(mime::APPLICATION_WWW_FORM_URLENCODED)
+    }
+
+    /// `application/octet-stream`
+    pub fn octet_stream() -> Self {
+        ContentType(mime::APPLICATION_OCTET_STREAM)
+    }
+
+    /// Returns the media type.
+    pub fn mime(&self) -> &Mime {
+        &self.0
+    }
+}
+
+impl From<Mime> for ContentType {
+    fn from(mime: Mime) -> Self {
+        ContentType(mime)
+    }
+}
+
+impl TypedHeader for ContentType {
+    const NAME: HeaderName = header::CONTENT_TYPE;
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        values[0]
+            .trim()
+            .parse()
+            .map(ContentType)
+            .map_err(|_| InvalidHeader("invalid media type"))
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        encode(self.0.to_string())
+    }
+}
+
+/// The `Accept` header.
+///
+/// Media types are ordered by quality, with the most preferred first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Accept(Vec<QualityItem<Mime>>);
+
+impl Accept {
+    /// Create an `Accept` header from a list of media types.
+    pub fn new(items: impl IntoIterator<Item = QualityItem<Mime>>) -> Self {
+        let mut items = items.into_iter().collect::<Vec<_>>();
+        items.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+        Accept(items)
+    }
+
+    /// Returns an iterator over the accepted media types, most preferred first.
+    pub fn iter(&self) -> impl Iterator<Item = &QualityItem<Mime>> {
+        self.0.iter()
+    }
+
+    /// Returns the quality of the given media type.
+    ///
+    /// The most specific matching range is used, so `text/html` takes
+    /// precedence over `text/*`, which takes precedence over `*/*`.
+    /// Returns `0.0` if the media type is not accepted.
+    pub fn quality(&self, mime: &Mime) -> f32 {
+        self.0
+            .iter()
+            .filter_map(|item| {
+                let range = &item.value;
+                let specificity = if range.type_() == mime::STAR {
+                    0
+                } else if range.type_() != mime.type_() {
+                    return None;
+                } else if range.subtype() == mime::STAR {
+                    1
+                } else if range.subtype() != mime.subtype() {
+                    return None;
+                } else {
+                    2
+                };
+
+                Some((specificity, item.quality))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, quality)| quality)
+            .unwrap_or(0.0)
+    }
+
+    /// Returns `true` if the given media type is acceptable.
+    pub fn accepts(&self, mime: &Mime) -> bool {
+        self.quality(mime) > 0.0
+    }
+
+    /// Returns the most preferred of the available media types, or `None`
+    /// if none of them are acceptable.
+    ///
+    /// Media types with equal quality are preferred in the order given.
+    pub fn preferred<'a>(&self, available: &'a [Mime]) -> Option<&'a Mime> {
+        preferred(available, |mime| self.quality(mime))
+    }
+}
+
+impl TypedHeader for Accept {
+    const NAME: HeaderName = header::ACCEPT;
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        parse_quality_list(values, |value| value.parse().ok()).map(Accept)
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        let items = self
+            .0
+            .iter()
+            .map(|item| encode_quality(item.value.as_ref(), item.quality))
+            .collect::<Vec<_>>();
+
+        encode(items.join(", "))
+    }
+}
+
+/// Returns the available value with the highest non-zero quality,
+/// preferring earlier values on ties.
+fn preferred<'a, T>(
+    available: impl IntoIterator<Item = &'a T>,
+    quality: impl Fn(&T) -> f32,
+) -> Option<&'a T>
+where
+    T: ?Sized + 'a,
+{
+    let mut best: Option<(&T, f32)> = None;
+
+    for value in available {
+        let quality = quality(value);
+
+        match best {
+            Some((_, best)) if quality <= best => {}
+            _ if quality > 0.0 => best = Some((value, quality)),
+            _ => {}
+        }
+    }
+
+    best.map(|(value, _)| value)
+}
+
+/// Returns the quality of the most specific matching token.
+///
+/// `specificity` returns `None` if a token does not match.
+fn token_quality(
+    items: &[QualityItem<String>],
+    specificity: impl Fn(&str) -> Option<usize>,
+) -> Option<f32> {
+    items
+        .iter()
+        .filter_map(|item| Some((specificity(&item.value)?, item.quality)))
+        .max_by_key(|(specificity, _)| *specificity)
+        .map(|(_, quality)| quality)
+}
+
+macro_rules! token_list {
+    ($(#[$doc:meta])* $ty:ident => $name:expr, $item:literal) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $ty(Vec<QualityItem<String>>);
+
+        impl $ty {
+            #[doc = concat!("Create the header from a list of ", $item, "s.")]
+            pub fn new(items: impl IntoIterator<Item = QualityItem<String>>) -> Self {
+                let mut items = items.into_iter().collect::<Vec<_>>();
+                items.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+                $ty(items)
+            }
+
+            #[doc = concat!("Returns an iterator over the accepted ", $item, "s, most preferred first.")]
+            pub fn iter(&self) -> impl Iterator<Item = &QualityItem<String>> {
+                self.0.iter()
+            }
+
+            #[doc = concat!("Returns `true` if the given ", $item, " is acceptable.")]
+            pub fn accepts(&self, value: &str) -> bool {
+                self.quality(value) > 0.0
+            }
+
+            #[doc = concat!("Returns the most preferred of the available ", $item, "s, or `None`")]
+            /// if none of them are acceptable.
+            ///
+            /// Values with equal quality are preferred in the order given.
+            pub fn preferred<'a>(&self, available: &[&'a str]) -> Option<&'a str> {
+                preferred(available.iter().copied(), |value| self.quality(value))
+            }
+        }
+
+        impl TypedHeader for $ty {
+            const NAME: HeaderName = $name;
+
+            fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+                parse_quality_list(values, |value| {
+                    Some(value.to_owned()).filter(|value| !value.contains(char::is_whitespace))
+                })
+                .map($ty)
+            }
+
+            fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+                let items = self
+                    .0
+                    .iter()
+                    .map(|item| encode_quality(&item.value, item.quality))
+                    .collect::<Vec<_>>();
+
+                encode(items.join(", "))
+            }
+        }
+    };
+}
+
+token_list! {
+    /// The `Accept-Language` header.
+    ///
+    /// Language ranges are ordered by quality, with the most preferred first.
+    AcceptLanguage => header::ACCEPT_LANGUAGE, "language"
+}
+
+impl AcceptLanguage {
+    /// Returns the quality of the given language tag.
+    ///
+    /// The most specific matching range is used, so `en-US` takes
+    /// precedence over `en`, which takes precedence over `*`. Returns
+    /// `0.0` if the language is not accepted.
+    pub fn quality(&self, tag: &str) -> f32 {
+        token_quality(&self.0, |range| {
+            if range == "*" {
+                return Some(0);
+            }
+
+            let prefix = tag.get(..range.len())?;
+            let rest = &tag[range.len()..];
+
+            if prefix.eq_ignore_ascii_case(range) && (rest.is_empty() || rest.starts_with('-')) {
+                Some(range.len())
+            } else {
+                None
+            }
+        })
+        .unwrap_or(0.0)
+    }
+}
+
+token_list! {
+    /// The `Accept-Encoding` header.
+    ///
+    /// Content codings are ordered by quality, with the most preferred first.
+    AcceptEncoding => header::ACCEPT_ENCODING, "content coding"
+}
+
+impl AcceptEncoding {
+    /// Returns the quality of the given content coding.
+    ///
+    /// `identity` is always acceptable unless it is excluded explicitly,
+    /// or through `*;q=0`. Returns `0.0` if the coding is not accepted.
+    pub fn quality(&self, coding: &str) -> f32 {
+        token_quality(&self.0, |value| match value {
+            "*" => Some(0),
+            value if value.eq_ignore_ascii_case(coding) => Some(1),
+            _ => None,
+        })
+        .unwrap_or(if coding.eq_ignore_ascii_case("identity") {
+            1.0
+        } else {
+            0.0
+        })
+    }
+}
+
+token_list! {
+    /// The `Accept-Charset` header.
+    ///
+    /// Charsets are ordered by quality, with the most preferred first.
+    AcceptCharset => header::ACCEPT_CHARSET, "charset"
+}
+
+impl AcceptCharset {
+    /// Returns the quality of the given charset.
+    ///
+    /// Returns `0.0` if the charset is not accepted.
+    pub fn quality(&self, charset: &str) -> f32 {
+        token_quality(&self.0, |value| match value {
+            "*" => Some(0),
+            value if value.eq_ignore_ascii_case(charset) => Some(1),
+            _ => None,
+        })
+        .unwrap_or(0.0)
+    }
+}
+
+/// The `Authorization` header.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Authorization {
+    /// Basic authentication, with a username and password.
+    Basic {
+        /// The username.
+        username: String,
+        /// The password.
+        password: String,
+    },
+    /// Bearer token authentication.
+    Bearer(String),
+}
+
+impl Authorization {
+    /// Create a `Basic` authorization header.
+    pub fn basic(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Authorization::Basic {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Create a `Bearer` authorization header.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Authorization::Bearer(token.into())
+    }
+}
+
+impl TypedHeader for Authorization {
+    const NAME: HeaderName = header::AUTHORIZATION;
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        let (scheme, credentials) = values[0]
+            .trim()
+            .split_once(' ')
+            .ok_or(InvalidHeader("missing authorization credentials"))?;
+
+        let credentials = credentials.trim();
+
+        if scheme.eq_ignore_ascii_case("basic") {
+            let decoded = base64::decode(credentials)
+                .ok()
+                .and_then(|decoded| String::from_utf8(decoded).ok())
+                .ok_or(InvalidHeader("invalid basic credentials"))?;
+
+            let (username, password) = decoded
+                .split_once(':')
+                .ok_or(InvalidHeader("invalid basic credentials"))?;
+
+            Ok(Authorization::basic(username, password))
+        } else if scheme.eq_ignore_ascii_case("bearer") {
+            Ok(Authorization::bearer(credentials))
+        } else {
+            Err(InvalidHeader("unsupported authorization scheme"))
+        }
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        match self {
+            Authorization::Basic { username, password } => {
+                let credentials = base64::encode(format!("{}:{}", username, password));
+                encode(format!("Basic {}", credentials))
+            }
+            Authorization::Bearer(token) => encode(format!("Bearer {}", token)),
+        }
+    }
+}
+
+/// An entity tag, as used by the `ETag` and `If-None-Match` headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag {
+    tag: String,
+    weak: bool,
+}
+
+impl ETag {
+    /// Create a strong entity tag.
+    pub fn strong(tag: impl Into<String>) -> Self {
+        ETag {
+            tag: tag.into(),
+            weak: false,
+        }
+    }
+
+    /// Create a weak entity tag.
+    pub fn weak(tag: impl Into<String>) -> Self {
+        ETag {
+            tag: tag.into(),
+            weak: true,
+        }
+    }
+
+    /// Returns the opaque tag, without quotes.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns `true` if this is a weak entity tag.
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        let (weak, tag) = match value.strip_prefix("W/") {
+            Some(tag) => (true, tag),
+            None => (false, value),
+        };
+
+        tag.strip_prefix('"')
+            .and_then(|tag| tag.strip_suffix('"'))
+            .filter(|tag| !tag.contains('"'))
+            .map(|tag| ETag {
+                tag: tag.to_owned(),
+                weak,
+            })
+            .ok_or(InvalidHeader("invalid entity tag"))
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/")?;
+        }
+
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+impl TypedHeader for ETag {
+    const NAME: HeaderName = header::ETAG;
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        ETag::parse(values[0].trim())
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        if self.tag.contains('"') {
+            return Err(InvalidHeader("invalid entity tag"));
+        }
+
+        encode(self.to_string())
+    }
+}
+
+/// The `If-None-Match` header.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IfNoneMatch {
+    /// Matches any current representation (`*`).
+    Any,
+    /// A list of entity tags.
+    Tags(Vec<ETag>),
+}
+
+impl IfNoneMatch {
+    /// Returns `true` if the given entity tag matches, meaning the
+    /// client already has the current representation.
+    ///
+    /// Entity tags are compared using weak comparison.
+    pub fn matches(&self, etag: &ETag) -> bool {
+        match self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Tags(tags) => tags.iter().any(|tag| tag.tag == etag.tag),
+        }
+    }
+}
+
+impl TypedHeader for IfNoneMatch {
+    const NAME: HeaderName = header::IF_NONE_MATCH;
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        if values[0].trim() == "*" {
+            return Ok(IfNoneMatch::Any);
+        }
+
+        split(values)
+            .map(ETag::parse)
+            .collect::<Result<_, _>>()
+            .map(IfNoneMatch::Tags)
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        match self {
+            IfNoneMatch::Any => Ok(HeaderValue::from_static("*")),
+            IfNoneMatch::Tags(tags) => {
+                if tags.iter().any(|tag| tag.tag.contains('"')) {
+                    return Err(InvalidHeader("invalid entity tag"));
+                }
+
+                let tags = tags.iter().map(ETag::to_string).collect::<Vec<_>>();
+                encode(tags.join(", "))
+            }
+        }
+    }
+}
+
+/// The `Range` header.
+///
+/// Only byte ranges are supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range(Vec<ByteRange>);
+
+/// A single range of bytes in a [`Range`] header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// The bytes from the first to the last position, inclusive (`0-499`).
+    Bounded(u64, u64),
+    /// The bytes from a position to the end (`500-`).
+    From(u64),
+    /// The last number of bytes (`-500`).
+    Last(u64),
+}
+
+impl ByteRange {
+    /// Resolve the range against a representation of the given length.
+    ///
+    /// Returns `None` if the range is not satisfiable.
+    pub fn resolve(&self, len: u64) -> Option<ops::Range<u64>> {
+        let range = match *self {
+            ByteRange::Bounded(start, end) => start..end.saturating_add(1).min(len),
+            ByteRange::From(start) => start..len,
+            ByteRange::Last(n) => len.saturating_sub(n)..len,
+        };
+
+        Some(range).filter(|range| range.start < range.end)
+    }
+}
+
+impl Range {
+    /// Create a `Range` header from a list of byte ranges.
+    pub fn bytes(ranges: impl IntoIterator<Item = ByteRange>) -> Self {
+        Range(ranges.into_iter().collect())
+    }
+
+    /// Returns the requested byte ranges.
+    pub fn ranges(&self) -> &[ByteRange] {
+        &self.0
+    }
+}
+
+impl TypedHeader for Range {
+    const NAME: HeaderName = header::RANGE;
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        let ranges = values[0]
+            .trim()
+            .strip_prefix("bytes=")
+            .ok_or(InvalidHeader("unsupported range unit"))?;
+
+        let parse = |n: &str| n.trim().parse::<u64>().ok();
+
+        split(&[ranges])
+            .map(|range| {
+                let range = match range.split_once('-') {
+                    Some(("", n)) => parse(n).map(ByteRange::Last),
+                    Some((start, "")) => parse(start).map(ByteRange::From),
+                    Some((start, end)) => match (parse(start), parse(end)) {
+                        (Some(start), Some(end)) if start <= end => {
+                            Some(ByteRange::Bounded(start, end))
+                        }
+                        _ => None,
+                    },
+                    None => None,
+                };
+
+                range.ok_or(InvalidHeader("invalid byte range"))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|ranges| {
+                if ranges.is_empty() {
+                    return Err(InvalidHeader("invalid byte range"));
+                }
+
+                Ok(Range(ranges))
+            })
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        let ranges = self
+            .0
+            .iter()
+            .map(|range| match range {
+                ByteRange::Bounded(start, end) => format!("{}-{}", start, end),
+                ByteRange::From(start) => format!("{}-", start),
+                ByteRange::Last(n) => format!("-{}", n),
+            })
+            .collect::<Vec<_>>();
+
+        encode(format!("bytes={}", ranges.join(", ")))
+    }
+}
+
+/// The `Cache-Control` header.
+///
+/// Unknown directives are ignored.
+///
+/// ```
+/// use bison::http::headers::CacheControl;
+/// use std::time::Duration;
+///
+/// let cache = CacheControl {
+///     private: true,
+///     max_age: Some(Duration::from_secs(300)),
+///     ..CacheControl::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    /// `no-cache`
+    pub no_cache: bool,
+    /// `no-store`
+    pub no_store: bool,
+    /// `no-transform`
+    pub no_transform: bool,
+    /// `only-if-cached`
+    pub only_if_cached: bool,
+    /// `must-revalidate`
+    pub must_revalidate: bool,
+    /// `proxy-revalidate`
+    pub proxy_revalidate: bool,
+    /// `public`
+    pub public: bool,
+    /// `private`
+    pub private: bool,
+    /// `immutable`
+    pub immutable: bool,
+    /// `max-age`
+    pub max_age: Option<Duration>,
+    /// `s-maxage`
+    pub s_max_age: Option<Duration>,
+    /// `max-stale`
+    pub max_stale: Option<Duration>,
+    /// `min-fresh`
+    pub min_fresh: Option<Duration>,
+}
+
+impl TypedHeader for CacheControl {
+    const NAME: HeaderName = header::CACHE_CONTROL;
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        let mut cache = CacheControl::default();
+
+        for directive in split(values) {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+
+            let seconds = || {
+                value
+                    .and_then(|value| value.parse().ok())
+                    .map(Duration::from_secs)
+                    .ok_or(InvalidHeader("invalid cache-control directive"))
+            };
+
+            match name.to_ascii_lowercase().as_str() {
+                "no-cache" => cache.no_cache = true,
+                "no-store" => cache.no_store = true,
+                "no-transform" => cache.no_transform = true,
+                "only-if-cached" => cache.only_if_cached = true,
+                "must-revalidate" => cache.must_revalidate = true,
+                "proxy-revalidate" => cache.proxy_revalidate = true,
+                "public" => cache.public = true,
+                "private" => cache.private = true,
+                "immutable" => cache.immutable = true,
+                "max-age" => cache.max_age = Some(seconds()?),
+                "s-maxage" => cache.s_max_age = Some(seconds()?),
+                "max-stale" => cache.max_stale = Some(seconds()?),
+                "min-fresh" => cache.min_fresh = Some(seconds()?),
+                _ => {}
+            }
+        }
+
+        Ok(cache)
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        let flags = [
+            (self.no_cache, "no-cache"),
+            (self.no_store, "no-store"),
+            (self.no_transform, "no-transform"),
+            (self.only_if_cached, "only-if-cached"),
+            (self.must_revalidate, "must-revalidate"),
+            (self.proxy_revalidate, "proxy-revalidate"),
+            (self.public, "public"),
+            (self.private, "private"),
+            (self.immutable, "immutable"),
+        ];
+
+        let durations = [
+            (self.max_age, "max-age"),
+            (self.s_max_age, "s-maxage"),
+            (self.max_stale, "max-stale"),
+            (self.min_fresh, "min-fresh"),
+        ];
+
+        let directives = flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, name)| name.to_string())
+            .chain(durations.iter().filter_map(|(duration, name)| {
+                duration.map(|duration| format!("{}={}", name, duration.as_secs()))
+            }))
+            .collect::<Vec<_>>();
+
+        encode(directives.join(", "))
+    }
+}
+
+/// The `User-Agent` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAgent(String);
+
+impl UserAgent {
+    /// Create a `User-Agent` header.
+    pub fn new(user_agent: impl Into<String>) -> Self {
+        UserAgent(user_agent.into())
+    }
+
+    /// Returns the user agent string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TypedHeader for UserAgent {
+    const NAME: HeaderName = header::USER_AGENT;
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        Ok(UserAgent(values[0].trim().to_owned()))
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        encode(self.0.clone())
+    }
+}
+
+/// The `Last-Event-ID` header.
+///
+/// Sent by clients reconnecting to a stream of
+/// [Server-Sent Events](crate::respond::Sse), with the ID of the last
+/// event they received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastEventId(String);
+
+impl LastEventId {
+    /// Create a `Last-Event-ID` header.
+    pub fn new(id: impl Into<String>) -> Self {
+        LastEventId(id.into())
+    }
+
+    /// Returns the event ID.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TypedHeader for LastEventId {
+    const NAME: HeaderName = HeaderName::from_static("last-event-id");
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        Ok(LastEventId(values[0].to_owned()))
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        encode(self.0.clone())
+    }
+}
+
+/// The `Cookie` header.
+///
+/// Cookies sent in multiple `Cookie` headers are combined.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cookie(Vec<(String, String)>);
+
+impl Cookie {
+    /// Create an empty `Cookie` header.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a cookie to the header.
+    pub fn add(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.0.push((name.into(), value.into()));
+        self
+    }
+
+    /// Returns the value of the first cookie with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns an iterator over the `(name, value)` pairs of all cookies.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl TypedHeader for Cookie {
+    const NAME: HeaderName = header::COOKIE;
+
+    fn decode(values: &[&str]) -> Result<Self, InvalidHeader> {
+        let cookies = values
+            .iter()
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(name, value)| {
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .unwrap_or(value);
+
+                (name.trim().to_owned(), value.to_owned())
+            })
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
+
+        Ok(Cookie(cookies))
+    }
+
+    fn encode(&self) -> Result<HeaderValue, InvalidHeader> {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>();
+
+        encode(pairs.join("; "))
+    }
+}
@@ -4,13 +4,26 @@ mod rcstr;
 pub use rcstr::RcStr;
 
 mod body;
-pub use body::Body;
+pub use body::{Body, BufferError};
+
+pub mod headers;
+
+crate::util::cfg_cookies! {
+    pub mod cookie;
+}
+
+mod upgrade;
+pub use upgrade::{OnUpgrade, Upgraded};
+
+crate::util::cfg_websocket! {
+    pub mod websocket;
+}
 
 pub(crate) mod request;
 pub use request::{Method, Request};
 
 pub use bytes::Bytes;
-pub use http::{header, Extensions, HeaderValue, StatusCode};
+pub use http::{header, Extensions, HeaderMap, HeaderValue, StatusCode};
 
 /// An HTTP response.
 ///
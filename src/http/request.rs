@@ -1,5 +1,5 @@
-use super::Body;
-use crate::bounded::{cfg_send, OnceCell, Rc, RefCell};
+use super::{Body, OnUpgrade};
+use crate::bounded::{cfg_send, Lock, Rc};
 use crate::state::{AppState, State};
 
 use std::any::{Any, TypeId};
@@ -35,13 +35,15 @@ pub struct Request {
 
 pub struct Shared {
     method: AtomicU8,
-    uri: RefCell<Uri>,
+    uri: Lock<Uri>,
     state: AppState,
     headers: Headers,
     cache: Cache,
     body: Body,
+    upgrade: Lock<Option<OnUpgrade>>,
+    route: Option<Rc<str>>,
     route_params: Params,
-    query_params: OnceCell<Params>,
+    query_params: QueryCache,
 }
 
 impl Request {
@@ -54,46 +56,47 @@ impl Request {
     }
 
     pub fn uri(&self) -> Uri {
-        self.shared.uri.borrow_mut().clone()
+        self.shared.uri.lock().clone()
     }
 
     pub fn set_uri(&self, uri: Uri) {
-        *self.shared.uri.borrow_mut() = uri;
+        *self.shared.uri.lock() = uri;
     }
 
     pub fn headers(&self) -> &Headers {
         &self.shared.headers
     }
 
+    /// Returns the route that matched the request, such as `/users/:id`.
+    pub fn route(&self) -> Option<&str> {
+        self.shared.route.as_deref()
+    }
+
     pub fn param(&self, name: &str) -> Option<&str> {
         self.shared.route_params.get(name)
     }
 
-    pub fn query(&self, name: &str) -> Option<&str> {
-        if let Some(query) = self.shared.uri.borrow_mut().query() {
-            return self
-                .shared
-                .query_params
-                .get_or_try_init(|| {
-                    serde_urlencoded::from_str::<Vec<(String, String)>>(query).map(Params)
-                })
-                .ok()
-                .and_then(|params| {
-                    params
-                        .0
-                        .iter()
-                        .find(|(n, _)| n == name)
-                        .map(|(_, value)| value.as_str())
-                });
-        }
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.route_params()
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
 
-        None
+    pub fn query(&self, name: &str) -> Option<&str> {
+        self.query_params().get(name)
     }
 
     pub fn body(&self) -> &Body {
         &self.shared.body
     }
 
+    /// Takes the pending protocol upgrade, if the server supports upgrades.
+    ///
+    /// This returns `None` if the upgrade has already been taken.
+    pub fn upgrade(&self) -> Option<OnUpgrade> {
+        self.shared.upgrade.lock().take()
+    }
+
     pub fn state<T>(&self) -> Option<&T>
     where
         T: State,
@@ -117,22 +120,38 @@ impl Request {
 }
 
 impl Request {
+    pub(crate) fn route_params(&self) -> &[(String, String)] {
+        self.shared.route_params.as_slice()
+    }
+
+    pub(crate) fn query_params(&self) -> &Params {
+        static EMPTY: Params = Params(Vec::new());
+
+        match self.shared.uri.lock().query() {
+            Some(query) => self.shared.query_params.get(query).unwrap_or(&EMPTY),
+            None => &EMPTY,
+        }
+    }
+
     pub(crate) fn new(
         req: http::Request<Body>,
         state: AppState,
+        route: Option<Rc<str>>,
         route_params: Params,
     ) -> Option<Self> {
-        let (req, body) = req.into_parts();
+        let (mut req, body) = req.into_parts();
 
         Some(Request {
             shared: Rc::new(Shared {
                 method: AtomicU8::new(Method::from_http(req.method)?.0),
-                uri: RefCell::new(Uri(req.uri)),
-                query_params: OnceCell::new(),
-                headers: Headers(RefCell::new(req.headers)),
+                uri: Lock::new(Uri(req.uri)),
+                query_params: QueryCache::default(),
+                headers: Headers(Lock::new(req.headers)),
                 cache: Cache::default(),
+                route,
                 route_params,
                 body,
+                upgrade: Lock::new(req.extensions.remove()),
                 state,
             }),
         })
@@ -163,17 +182,32 @@ impl FromStr for Uri {
 }
 
 #[derive(Clone, PartialEq, Default)]
-pub struct Headers(RefCell<http::HeaderMap>);
+pub struct Headers(Lock<http::HeaderMap>);
 
 impl Headers {
     pub fn get(&self, key: http::header::HeaderName) -> Option<HeaderValue> {
         self.0
-            .borrow_mut()
+            .lock()
             .get(key)
             .filter(|value| value.to_str().is_ok())
             .cloned()
             .map(HeaderValue)
     }
+
+    pub(crate) fn to_map(&self) -> http::HeaderMap {
+        self.0.lock().clone()
+    }
+
+    pub fn get_all(&self, key: http::header::HeaderName) -> Vec<HeaderValue> {
+        self.0
+            .lock()
+            .get_all(key)
+            .iter()
+            .filter(|value| value.to_str().is_ok())
+            .cloned()
+            .map(HeaderValue)
+            .collect()
+    }
 }
 
 pub struct HeaderValue(http::HeaderValue);
@@ -228,6 +262,10 @@ impl Params {
             .find(|(key, _)| key == name)
             .map(|(_, val)| val.as_ref())
     }
+
+    pub(crate) fn as_slice(&self) -> &[(String, String)] {
+        &self.0
+    }
 }
 
 impl FromIterator<(String, String)> for Params {
@@ -244,7 +282,7 @@ type AnyMap = HashMap<TypeId, Box<dyn Any + Send + Sync>, BuildHasherDefault<Ide
 #[derive(Default)]
 struct Cache {
     map: UnsafeCell<AnyMap>,
-    guard: RefCell<()>,
+    guard: Lock<()>,
 }
 
 impl Cache {
@@ -252,7 +290,7 @@ impl Cache {
     where
         T: Send + Sync + 'static,
     {
-        let borrowed = self.guard.borrow_mut();
+        let borrowed = self.guard.lock();
         // SAFETY: `borrowed` guarantees mutual exclusion,
         // and we can return a borrow because values are
         // boxed and so have a stable address
@@ -269,7 +307,7 @@ impl Cache {
     where
         T: Send + Sync + 'static,
     {
-        let borrowed = self.guard.borrow_mut();
+        let borrowed = self.guard.lock();
         let id = TypeId::of::<T>();
         let map = self.map.get();
         // SAFETY: `borrowed` guarantees mutual exclusion
@@ -286,9 +324,50 @@ impl Cache {
 }
 
 cfg_send! {
-    // SAFETY: all accesses of the map are done through the RefCell
-    unsafe impl Send for Cache where RefCell<()>: Send {}
-    unsafe impl Sync for Cache where RefCell<()>: Sync {}
+    // SAFETY: all accesses of the map are done through the lock
+    unsafe impl Send for Cache where Lock<()>: Send {}
+    unsafe impl Sync for Cache where Lock<()>: Sync {}
+}
+
+/// Parsed query strings, keyed by the query they were parsed from.
+///
+/// The uri can be changed after the query has been read, so
+/// entries are never replaced or removed while the request lives.
+#[derive(Default)]
+struct QueryCache {
+    parsed: UnsafeCell<Vec<(String, Box<Params>)>>,
+    guard: Lock<()>,
+}
+
+impl QueryCache {
+    /// Returns the parameters of the query, or `None` if it fails to parse.
+    fn get(&self, query: &str) -> Option<&Params> {
+        let borrowed = self.guard.lock();
+        let parsed = self.parsed.get();
+        // SAFETY: `borrowed` guarantees mutual exclusion,
+        // and we can return a borrow because entries are
+        // boxed and never removed
+        let params = unsafe {
+            match (*parsed).iter().find(|(key, _)| key == query) {
+                Some((_, params)) => Some(&**params),
+                None => match serde_urlencoded::from_str(query) {
+                    Ok(params) => {
+                        (*parsed).push((query.to_owned(), Box::new(Params(params))));
+                        (*parsed).last().map(|(_, params)| &**params)
+                    }
+                    Err(_) => None,
+                },
+            }
+        };
+        drop(borrowed);
+        params
+    }
+}
+
+cfg_send! {
+    // SAFETY: all accesses of the entries are done through the lock
+    unsafe impl Send for QueryCache where Lock<()>: Send {}
+    unsafe impl Sync for QueryCache where Lock<()>: Sync {}
 }
 
 #[derive(Default)]
@@ -0,0 +1,115 @@
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+type UpgradeFuture = Pin<Box<dyn Future<Output = io::Result<Upgraded>> + Send>>;
+
+/// A pending protocol upgrade.
+///
+/// Servers that support upgrades insert this into the extensions of each
+/// request, where it can be taken with [`Request::upgrade`](crate::Request::upgrade).
+/// The future resolves to the upgraded connection once a
+/// `101 Switching Protocols` response has been sent.
+pub struct OnUpgrade {
+    // the future is never polled through a shared reference,
+    // the lock only makes it `Sync` to fit in the extensions
+    future: Mutex<UpgradeFuture>,
+}
+
+impl OnUpgrade {
+    /// Create an upgrade from a future resolving to the upgraded connection.
+    pub fn new<F, I, E>(future: F) -> Self
+    where
+        F: Future<Output = Result<I, E>> + Send + 'static,
+        I: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let future = async move {
+            match future.await {
+                Ok(io) => Ok(Upgraded { io: Box::new(io) }),
+                Err(err) => Err(io::Error::other(err)),
+            }
+        };
+
+        OnUpgrade {
+            future: Mutex::new(Box::pin(future)),
+        }
+    }
+}
+
+impl Future for OnUpgrade {
+    type Output = io::Result<Upgraded>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.get_mut().future.get_mut().unwrap();
+        future.as_mut().poll(cx)
+    }
+}
+
+impl fmt::Debug for OnUpgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnUpgrade").finish()
+    }
+}
+
+trait Io: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T> Io for T where T: AsyncRead + AsyncWrite + Send + Unpin {}
+
+/// An upgraded connection.
+///
+/// See [`OnUpgrade`] for details.
+pub struct Upgraded {
+    io: Box<dyn Io>,
+}
+
+impl AsyncRead for Upgraded {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Upgraded {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.io.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.io).poll_shutdown(cx)
+    }
+}
+
+impl fmt::Debug for Upgraded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Upgraded").finish()
+    }
+}
@@ -0,0 +1,363 @@
+//! WebSocket connections.
+//!
+//! The [`websocket`](crate::extract::websocket) extractor validates the
+//! handshake of a WebSocket request and returns a [`WebSocketUpgrade`].
+//! Responding with [`on_upgrade`](WebSocketUpgrade::on_upgrade) switches
+//! protocols and calls the given function with the connected [`WebSocket`]:
+//!
+//! ```
+//! use bison::Context;
+//! use bison::extract::websocket;
+//! use bison::http::websocket::{Message, WebSocketUpgrade};
+//! use bison::Respond;
+//!
+//! #[derive(Context)]
+//! struct Echo {
+//!     #[cx(websocket)]
+//!     ws: WebSocketUpgrade,
+//! }
+//!
+//! async fn echo(cx: Echo) -> impl Respond {
+//!     cx.ws.on_upgrade(|mut socket| async move {
+//!         while let Some(Ok(message)) = socket.recv().await {
+//!             if let Message::Text(text) = message {
+//!                 if socket.send(Message::Text(text)).await.is_err() {
+//!                     break;
+//!                 }
+//!             }
+//!         }
+//!     })
+//! }
+//! ```
+//!
+//! Upgrades must be supported by the server, which hands the connection to
+//! the application through an [`OnUpgrade`] in the request extensions.
+//!
+//! With the `not-send` feature, the socket is run with
+//! [`spawn_local`](tokio::task::spawn_local), so the application must be
+//! served from within a [`LocalSet`](tokio::task::LocalSet).
+
+use crate::bounded::Send;
+use crate::http::{
+    header, Body, HeaderValue, OnUpgrade, Response, ResponseBuilder, StatusCode, Upgraded,
+};
+use crate::Respond;
+
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use futures_util::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::{self, frame, Role, WebSocketConfig};
+use tokio_tungstenite::WebSocketStream;
+
+/// A validated WebSocket handshake, extracted with the
+/// [`websocket`](crate::extract::websocket) extractor.
+///
+/// See the [module level documentation](self) for details.
+pub struct WebSocketUpgrade {
+    key: String,
+    requested: Vec<String>,
+    protocol: Option<String>,
+    max_message_size: Option<usize>,
+    on_upgrade: OnUpgrade,
+}
+
+impl WebSocketUpgrade {
+    pub(crate) fn new(key: String, requested: Vec<String>, on_upgrade: OnUpgrade) -> Self {
+        WebSocketUpgrade {
+            key,
+            requested,
+            protocol: None,
+            max_message_size: None,
+            on_upgrade,
+        }
+    }
+
+    /// Set the subprotocols supported by the server, in order of preference.
+    ///
+    /// The first of them requested by the client in the
+    /// `Sec-WebSocket-Protocol` header is selected.
+    pub fn protocols<I>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.protocol = protocols
+            .into_iter()
+            .find(|protocol| {
+                self.requested
+                    .iter()
+                    .any(|requested| requested == protocol.as_ref())
+            })
+            .map(|protocol| protocol.as_ref().to_owned());
+        self
+    }
+
+    /// Returns the selected subprotocol, if any.
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    /// Set the maximum size of an incoming message.
+    ///
+    /// Defaults to 64 MiB.
+    pub fn max_message_size(mut self, size: usize) -> Self {
+        self.max_message_size = Some(size);
+        self
+    }
+
+    /// Switch protocols, calling the function with the connected socket.
+    ///
+    /// The function runs in a separate task once the response has been sent.
+    ///
+    /// # Panics
+    ///
+    /// With the `not-send` feature, responding panics if the application
+    /// is not running within a [`LocalSet`](tokio::task::LocalSet).
+    pub fn on_upgrade<F, Fut>(self, f: F) -> OnWebSocket<F>
+    where
+        F: FnOnce(WebSocket) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        OnWebSocket {
+            upgrade: self,
+            callback: f,
+        }
+    }
+}
+
+impl fmt::Debug for WebSocketUpgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketUpgrade")
+            .field("protocol", &self.protocol)
+            .field("max_message_size", &self.max_message_size)
+            .finish()
+    }
+}
+
+/// Responds with `101 Switching Protocols`, and then runs a function
+/// with the connected [`WebSocket`].
+///
+/// See [`WebSocketUpgrade::on_upgrade`] for details.
+pub struct OnWebSocket<F> {
+    upgrade: WebSocketUpgrade,
+    callback: F,
+}
+
+impl<F, Fut> Respond for OnWebSocket<F>
+where
+    F: FnOnce(WebSocket) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    type Rejection = Infallible;
+
+    fn respond(self) -> Result<Response, Infallible> {
+        let OnWebSocket { upgrade, callback } = self;
+        let accept = derive_accept_key(upgrade.key.as_bytes());
+
+        let mut response = ResponseBuilder::new()
+            .status(StatusCode::SWITCHING_PROTOCOLS)
+            .header(header::CONNECTION, "upgrade")
+            .header(header::UPGRADE, "websocket")
+            .header(header::SEC_WEBSOCKET_ACCEPT, accept);
+
+        if let Some(protocol) = &upgrade.protocol {
+            // protocols are only selected from valid request headers
+            let protocol = HeaderValue::from_str(protocol).unwrap();
+            response = response.header(header::SEC_WEBSOCKET_PROTOCOL, protocol);
+        }
+
+        let mut config = WebSocketConfig::default();
+        if let Some(size) = upgrade.max_message_size {
+            config.max_message_size = Some(size);
+        }
+
+        let protocol = upgrade.protocol;
+        spawn(async move {
+            let io = match upgrade.on_upgrade.await {
+                Ok(io) => io,
+                Err(_) => return,
+            };
+
+            let inner = WebSocketStream::from_raw_socket(io, Role::Server, Some(config)).await;
+            callback(WebSocket { inner, protocol }).await;
+        });
+
+        Ok(response.body(Body::empty()).unwrap())
+    }
+}
+
+impl<F> fmt::Debug for OnWebSocket<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnWebSocket")
+            .field("upgrade", &self.upgrade)
+            .finish()
+    }
+}
+
+crate::bounded::cfg_send! {
+    fn spawn(future: impl Future<Output = ()> + Send + 'static) {
+        tokio::spawn(future);
+    }
+}
+
+crate::bounded::cfg_not_send! {
+    fn spawn(future: impl Future<Output = ()> + 'static) {
+        tokio::task::spawn_local(future);
+    }
+}
+
+/// A connected WebSocket.
+///
+/// Pings are answered automatically while the socket is being read from.
+pub struct WebSocket {
+    inner: WebSocketStream<Upgraded>,
+    protocol: Option<String>,
+}
+
+impl WebSocket {
+    /// Receive the next message, returning `None` once the connection is closed.
+    pub async fn recv(&mut self) -> Option<Result<Message, WebSocketError>> {
+        self.next().await
+    }
+
+    /// Send a message.
+    pub async fn send(&mut self, message: Message) -> Result<(), WebSocketError> {
+        self.inner
+            .send(message.into_tungstenite())
+            .await
+            .map_err(WebSocketError)
+    }
+
+    /// Close the connection.
+    pub async fn close(mut self) -> Result<(), WebSocketError> {
+        self.inner.close(None).await.map_err(WebSocketError)
+    }
+
+    /// Returns the subprotocol selected during the handshake, if any.
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+}
+
+impl Stream for WebSocket {
+    type Item = Result<Message, WebSocketError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match self.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(message))) => message,
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(WebSocketError(err)))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            // raw frames are never returned when reading
+            if let Some(message) = Message::from_tungstenite(message) {
+                return Poll::Ready(Some(Ok(message)));
+            }
+        }
+    }
+}
+
+impl fmt::Debug for WebSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocket")
+            .field("protocol", &self.protocol)
+            .finish()
+    }
+}
+
+/// A WebSocket message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// A UTF-8 text message.
+    Text(String),
+    /// A binary message.
+    Binary(Vec<u8>),
+    /// A ping, with up to 125 bytes of data.
+    Ping(Vec<u8>),
+    /// A pong, with up to 125 bytes of data.
+    Pong(Vec<u8>),
+    /// A close message, with an optional reason.
+    Close(Option<CloseFrame>),
+}
+
+/// The reason a WebSocket connection was closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloseFrame {
+    /// The close code, such as `1000` for a normal closure.
+    pub code: u16,
+    /// The reason for the closure.
+    pub reason: String,
+}
+
+impl Message {
+    fn into_tungstenite(self) -> protocol::Message {
+        match self {
+            Message::Text(text) => protocol::Message::Text(text),
+            Message::Binary(data) => protocol::Message::Binary(data),
+            Message::Ping(data) => protocol::Message::Ping(data),
+            Message::Pong(data) => protocol::Message::Pong(data),
+            Message::Close(frame) => {
+                protocol::Message::Close(frame.map(|frame| protocol::CloseFrame {
+                    code: frame::coding::CloseCode::from(frame.code),
+                    reason: frame.reason.into(),
+                }))
+            }
+        }
+    }
+
+    fn from_tungstenite(message: protocol::Message) -> Option<Self> {
+        Some(match message {
+            protocol::Message::Text(text) => Message::Text(text),
+            protocol::Message::Binary(data) => Message::Binary(data),
+            protocol::Message::Ping(data) => Message::Ping(data),
+            protocol::Message::Pong(data) => Message::Pong(data),
+            protocol::Message::Close(frame) => Message::Close(frame.map(|frame| CloseFrame {
+                code: frame.code.into(),
+                reason: frame.reason.into_owned(),
+            })),
+            protocol::Message::Frame(_) => return None,
+        })
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Message::Text(text)
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        Message::Text(text.to_owned())
+    }
+}
+
+impl From<Vec<u8>> for Message {
+    fn from(data: Vec<u8>) -> Self {
+        Message::Binary(data)
+    }
+}
+
+/// An error that occurred while reading from or writing to a [`WebSocket`].
+#[derive(Debug)]
+pub struct WebSocketError(tokio_tungstenite::tungstenite::Error);
+
+impl fmt::Display for WebSocketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "websocket error: {}", self.0)
+    }
+}
+
+impl std::error::Error for WebSocketError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
@@ -1,5 +1,8 @@
+// multer requires a `Send` body stream
+#[cfg(all(feature = "multipart", feature = "not-send"))]
+compile_error!("the `multipart` feature cannot be used with `not-send`");
+
 mod bison;
-mod respond;
 mod router;
 mod state;
 mod util;
@@ -9,6 +12,8 @@ pub mod extract;
 pub mod handler;
 pub mod http;
 pub mod reject;
+pub mod respond;
+pub mod validate;
 pub mod wrap;
 
 util::doc_inline! {
@@ -20,7 +25,8 @@ util::doc_inline! {
     pub use self::respond::Respond;
     pub use self::router::Scope;
     pub use self::state::State;
-    pub use bison_codegen::Context;
+    pub use self::validate::Validate;
+    pub use bison_codegen::{Context, Reject, Respond, Validate};
 }
 
 /// A macro for async-trait methods.
@@ -0,0 +1,222 @@
+use super::{Rejection, ResponseRejection};
+use crate::extract::decode_typed_header;
+use crate::http::headers::Accept;
+use crate::http::{header, Body, HeaderValue, Request, Response};
+use crate::util::escape_json;
+
+use std::backtrace::BacktraceStatus;
+use std::fmt::Write;
+
+/// Whether rejections render debug pages, set with [`Bison::debug`](crate::Bison::debug).
+#[derive(Clone, Copy)]
+pub(crate) struct DebugMode(pub(crate) bool);
+
+pub(crate) fn enabled(req: &Request) -> bool {
+    req.state::<DebugMode>().is_some_and(|debug| debug.0)
+}
+
+/// Everything shown on a debug page.
+struct Details {
+    message: String,
+    debug: String,
+    sources: Vec<String>,
+    backtrace: Option<String>,
+    method: String,
+    uri: String,
+    route: Option<String>,
+    params: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+}
+
+/// Render the rejection as a debug page, keeping the status and
+/// headers of the response it would otherwise reject with.
+pub(crate) fn render(rejection: Rejection, req: &Request) -> Response {
+    debug_assert!(!rejection.is::<ResponseRejection>());
+
+    let mut sources = Vec::new();
+    let mut source = rejection.source();
+    while let Some(err) = source {
+        sources.push(err.to_string());
+        source = err.source();
+    }
+
+    let backtrace = match rejection.backtrace().status() {
+        BacktraceStatus::Captured => Some(rejection.backtrace().to_string()),
+        _ => None,
+    };
+
+    let details = Details {
+        message: rejection.to_string(),
+        debug: format!("{:#?}", rejection),
+        sources,
+        backtrace,
+        method: req.method().into_http().to_string(),
+        uri: req.uri().to_string(),
+        route: req.route().map(str::to_owned),
+        params: req
+            .params()
+            .map(|(name, value)| (name.to_owned(), value.to_owned()))
+            .collect(),
+        headers: req
+            .headers()
+            .to_map()
+            .iter()
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                (name.to_string(), value)
+            })
+            .collect(),
+    };
+
+    let mut response = rejection.inner.reject(req);
+    let status = response.status();
+
+    let (content_type, body) = if prefers_json(req) {
+        ("application/json", json(status.as_u16(), &details))
+    } else {
+        let title = match status.canonical_reason() {
+            Some(reason) => format!("{} {}", status.as_u16(), reason),
+            None => status.as_u16().to_string(),
+        };
+        ("text/html; charset=utf-8", html(&title, &details))
+    };
+
+    let headers = response.headers_mut();
+    headers.remove(header::CONTENT_LENGTH);
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    *response.body_mut() = Body::once(body);
+    response
+}
+
+fn prefers_json(req: &Request) -> bool {
+    let available = [mime::TEXT_HTML, mime::APPLICATION_JSON];
+
+    match decode_typed_header::<Accept>(req) {
+        Ok(Some(accept)) => accept.preferred(&available) == Some(&available[1]),
+        _ => false,
+    }
+}
+
+fn json(status: u16, details: &Details) -> String {
+    fn string(out: &mut String, key: &str, value: Option<&str>) {
+        let _ = write!(out, r#","{}":"#, key);
+        match value {
+            Some(value) => escape_json(out, value),
+            None => out.push_str("null"),
+        }
+    }
+
+    fn map(out: &mut String, key: &str, entries: &[(String, String)]) {
+        let _ = write!(out, r#","{}":{{"#, key);
+        for (i, (name, value)) in entries.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            escape_json(out, name);
+            out.push(':');
+            escape_json(out, value);
+        }
+        out.push('}');
+    }
+
+    let mut out = format!(r#"{{"status":{}"#, status);
+    string(&mut out, "message", Some(&details.message));
+    string(&mut out, "debug", Some(&details.debug));
+
+    out.push_str(r#","sources":["#);
+    for (i, source) in details.sources.iter().enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+        escape_json(&mut out, source);
+    }
+    out.push(']');
+
+    string(&mut out, "method", Some(&details.method));
+    string(&mut out, "uri", Some(&details.uri));
+    string(&mut out, "route", details.route.as_deref());
+    map(&mut out, "params", &details.params);
+    map(&mut out, "headers", &details.headers);
+    string(&mut out, "backtrace", details.backtrace.as_deref());
+    out.push('}');
+    out
+}
+
+fn html(title: &str, details: &Details) -> String {
+    fn table(out: &mut String, entries: &[(String, String)]) {
+        out.push_str("<table>");
+        for (name, value) in entries {
+            out.push_str("<tr><th>");
+            escape_html(out, name);
+            out.push_str("</th><td>");
+            escape_html(out, value);
+            out.push_str("</td></tr>");
+        }
+        out.push_str("</table>");
+    }
+
+    let mut out = String::from("<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>");
+    escape_html(&mut out, title);
+    out.push_str(
+        "</title><style>body{font-family:sans-serif;margin:2em}\
+         pre{background:#f4f4f4;padding:1em;overflow:auto}\
+         th{text-align:left;padding-right:1em}</style></head><body><h1>",
+    );
+    escape_html(&mut out, title);
+    out.push_str("</h1><p>");
+    escape_html(&mut out, &details.message);
+    out.push_str("</p><h2>Error</h2><pre>");
+    escape_html(&mut out, &details.debug);
+    out.push_str("</pre>");
+
+    if !details.sources.is_empty() {
+        out.push_str("<h2>Caused by</h2><ol>");
+        for source in &details.sources {
+            out.push_str("<li>");
+            escape_html(&mut out, source);
+            out.push_str("</li>");
+        }
+        out.push_str("</ol>");
+    }
+
+    out.push_str("<h2>Request</h2>");
+    let request = [
+        ("Method".to_owned(), details.method.clone()),
+        ("URI".to_owned(), details.uri.clone()),
+        (
+            "Route".to_owned(),
+            details.route.clone().unwrap_or_else(|| "-".to_owned()),
+        ),
+    ];
+    table(&mut out, &request);
+
+    if !details.params.is_empty() {
+        out.push_str("<h2>Route Parameters</h2>");
+        table(&mut out, &details.params);
+    }
+
+    out.push_str("<h2>Headers</h2>");
+    table(&mut out, &details.headers);
+
+    if let Some(backtrace) = &details.backtrace {
+        out.push_str("<h2>Backtrace</h2><pre>");
+        escape_html(&mut out, backtrace);
+        out.push_str("</pre>");
+    }
+
+    out.push_str("</body></html>");
+    out
+}
+
+fn escape_html(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+}
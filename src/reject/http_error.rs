@@ -0,0 +1,192 @@
+use super::{ErrorResponse, Reject};
+use crate::bounded::BoxError;
+use crate::http::header::{self, HeaderName};
+use crate::http::{Body, Bytes, HeaderMap, HeaderValue, Request, Response, StatusCode};
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::time::Duration;
+
+/// A general purpose rejection with a status code, headers, and an
+/// optional body and source error.
+///
+/// Without a body, the message is rendered in the configured
+/// [`ErrorFormat`](super::ErrorFormat). The headers are added to the
+/// response either way.
+///
+/// ```
+/// use bison::http::StatusCode;
+/// use bison::reject::HttpError;
+/// use std::time::Duration;
+///
+/// let error = HttpError::too_many_requests()
+///     .message("slow down")
+///     .retry_after(Duration::from_secs(30));
+///
+/// let error = HttpError::new(StatusCode::BAD_GATEWAY)
+///     .with_source(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+/// ```
+#[derive(Debug)]
+pub struct HttpError {
+    status: StatusCode,
+    message: Option<String>,
+    headers: HeaderMap,
+    body: Option<Bytes>,
+    source: Option<BoxError>,
+}
+
+impl HttpError {
+    /// Create an error with the given status code.
+    pub fn new(status: StatusCode) -> Self {
+        HttpError {
+            status,
+            message: None,
+            headers: HeaderMap::new(),
+            body: None,
+            source: None,
+        }
+    }
+
+    /// A `400 Bad Request` error.
+    pub fn bad_request() -> Self {
+        Self::new(StatusCode::BAD_REQUEST)
+    }
+
+    /// A `401 Unauthorized` error, with a `WWW-Authenticate` header
+    /// containing the given challenge, such as `Bearer realm="api"`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the challenge is not a valid header value.
+    pub fn unauthorized(challenge: &str) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED)
+            .header(header::WWW_AUTHENTICATE, header_value(challenge))
+    }
+
+    /// A `403 Forbidden` error.
+    pub fn forbidden() -> Self {
+        Self::new(StatusCode::FORBIDDEN)
+    }
+
+    /// A `404 Not Found` error.
+    pub fn not_found() -> Self {
+        Self::new(StatusCode::NOT_FOUND)
+    }
+
+    /// A `405 Method Not Allowed` error, with an `Allow` header
+    /// listing the allowed methods.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if any of the methods is not a valid header value.
+    pub fn method_not_allowed(allowed: &[&str]) -> Self {
+        Self::new(StatusCode::METHOD_NOT_ALLOWED)
+            .header(header::ALLOW, header_value(&allowed.join(", ")))
+    }
+
+    /// A `409 Conflict` error.
+    pub fn conflict() -> Self {
+        Self::new(StatusCode::CONFLICT)
+    }
+
+    /// A `429 Too Many Requests` error.
+    ///
+    /// See [`retry_after`](HttpError::retry_after) for telling the client
+    /// when to try again.
+    pub fn too_many_requests() -> Self {
+        Self::new(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// A `500 Internal Server Error` error.
+    pub fn internal_server_error() -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// A `503 Service Unavailable` error.
+    ///
+    /// See [`retry_after`](HttpError::retry_after) for telling the client
+    /// when to try again.
+    pub fn service_unavailable() -> Self {
+        Self::new(StatusCode::SERVICE_UNAVAILABLE)
+    }
+
+    /// Set the error message.
+    ///
+    /// Defaults to the reason phrase of the status code.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Add a header to the response.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Set a `Retry-After` header, in whole seconds.
+    pub fn retry_after(self, delay: Duration) -> Self {
+        self.header(header::RETRY_AFTER, HeaderValue::from(delay.as_secs()))
+    }
+
+    /// Respond with the given body instead of rendering the message.
+    ///
+    /// The `Content-Type` should be set with [`header`](HttpError::header).
+    pub fn body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Set the error that caused this one.
+    ///
+    /// This is returned by [`Reject::source`].
+    pub fn with_source(mut self, source: impl Into<BoxError>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Returns the headers added to the response.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+fn header_value(value: &str) -> HeaderValue {
+    HeaderValue::from_str(value).expect("invalid header value")
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => f.write_str(message),
+            None => f.write_str(self.status.canonical_reason().unwrap_or("unknown error")),
+        }
+    }
+}
+
+impl Reject for HttpError {
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.source {
+            Some(source) => Some(&**source),
+            None => None,
+        }
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let mut response = match self.body {
+            Some(body) => {
+                let mut response = Response::new(Body::once(body));
+                *response.status_mut() = self.status;
+                response
+            }
+            None => ErrorResponse::new(self.status, self.to_string()).into_response(req),
+        };
+
+        response.headers_mut().extend(self.headers);
+        response
+    }
+}
@@ -0,0 +1,495 @@
+//! HTTP error handling.
+
+mod debug;
+pub(crate) use debug::DebugMode;
+
+mod http_error;
+pub use http_error::HttpError;
+
+use crate::bounded::{Send, Sync};
+use crate::http::{header, Body, HeaderValue, Response, ResponseBuilder, StatusCode};
+use crate::util::escape_json;
+use crate::wrap::{Next, Wrap};
+use crate::Request;
+
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::convert::Infallible;
+use std::error::Error as StdError;
+use std::fmt::{self, Debug, Display, Write};
+use std::sync::Arc;
+
+/// An error capable rejecting a request with an HTTP error esponse.
+///
+/// This trait can be derived for structs and enums, along with [`Display`].
+/// Each variant sets the status code of the response, and optionally a
+/// message that can refer to its fields. The response is rendered with
+/// [`ErrorResponse`].
+///
+/// ```
+/// use bison::Reject;
+///
+/// #[derive(Debug, Reject)]
+/// enum UserError {
+///     #[reject(status = 404, message = "user {id} does not exist")]
+///     NotFound { id: u64 },
+///     #[reject(status = 403, message = "{0} may not view this user")]
+///     Forbidden(String),
+///     // the message defaults to the reason phrase, "Service Unavailable"
+///     #[reject(status = 503)]
+///     Unavailable,
+/// }
+/// ```
+pub trait Reject: Debug + Display + Send + Sync {
+    /// Returns the status code of the response this error rejects with.
+    ///
+    /// This is a hint that lets middleware inspect a rejection without
+    /// turning it into a response. Defaults to `500 Internal Server Error`.
+    fn status(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+
+    /// Returns the lower-level source of this error, if any.
+    ///
+    /// The source chain is shown on the error pages rendered in
+    /// [debug mode](crate::Bison::debug).
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        None
+    }
+
+    /// Reject the request with an HTTP error esponse
+    fn reject(self, req: &Request) -> Response;
+}
+
+impl Reject for Infallible {
+    fn status(&self) -> StatusCode {
+        match *self {}
+    }
+
+    fn reject(self, _: &Request) -> Response {
+        unreachable!()
+    }
+}
+
+/// A dynamically typed rejection.
+pub struct Rejection {
+    inner: Box<dyn BoxedReject>,
+    backtrace: Backtrace,
+}
+
+impl Rejection {
+    /// Create a new `AnyResponseError` from a given response error.
+    // TODO: remove
+    pub fn new<E>(err: E) -> Self
+    where
+        // TODO: remove
+        E: IntoRejection,
+    {
+        err.into_response_error()
+    }
+
+    /// Convert this error into an HTTP response.
+    ///
+    /// This method is analogous to [`Reject::reject`],
+    /// which cannot be implemented directly due to coherence rules.
+    ///
+    /// In [debug mode](crate::Bison::debug), the response body is
+    /// replaced with a page describing the error and the request.
+    pub fn reject(self, req: &Request) -> Response {
+        if debug::enabled(req) && !self.is::<ResponseRejection>() {
+            return debug::render(self, req);
+        }
+
+        self.inner.reject(req)
+    }
+
+    /// Returns the status code hint of the underlying error.
+    ///
+    /// See [`Reject::status`] for details.
+    pub fn status(&self) -> StatusCode {
+        Reject::status(&*self.inner)
+    }
+
+    /// Returns the source of the underlying error.
+    ///
+    /// See [`Reject::source`] for details.
+    pub fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Reject::source(&*self.inner)
+    }
+
+    /// Returns the backtrace captured when the rejection was created.
+    ///
+    /// Backtraces are only captured if the `RUST_BACKTRACE` or
+    /// `RUST_LIB_BACKTRACE` environment variables are set.
+    /// See [`Backtrace::capture`] for details.
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+
+    /// Returns `true` if the underlying error is of type `T`.
+    pub fn is<T>(&self) -> bool
+    where
+        T: Reject + 'static,
+    {
+        self.inner.as_any().is::<T>()
+    }
+
+    /// Returns a reference to the underlying error if it is of type `T`.
+    ///
+    /// ```
+    /// use bison::extract::QueryRejection;
+    /// use bison::http::StatusCode;
+    /// use bison::reject::NotFound;
+    /// use bison::Rejection;
+    ///
+    /// let rejection = Rejection::from(NotFound);
+    /// assert!(rejection.downcast_ref::<NotFound>().is_some());
+    /// assert!(rejection.downcast_ref::<QueryRejection>().is_none());
+    /// assert_eq!(rejection.status(), StatusCode::NOT_FOUND);
+    /// ```
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: Reject + 'static,
+    {
+        self.inner.as_any().downcast_ref()
+    }
+
+    /// Attempts to downcast the rejection to the underlying error of type `T`,
+    /// returning the rejection unchanged if it is of a different type.
+    pub fn downcast<T>(self) -> Result<T, Self>
+    where
+        T: Reject + 'static,
+    {
+        if self.is::<T>() {
+            Ok(*self.inner.into_any().downcast().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+trait BoxedReject: Reject {
+    fn reject(self: Box<Self>, _: &Request) -> Response;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Reject + 'static> BoxedReject for T {
+    fn reject(self: Box<Self>, req: &Request) -> Response {
+        Reject::reject(*self, req)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl fmt::Debug for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+impl<E> From<E> for Rejection
+where
+    E: Reject + 'static,
+{
+    fn from(err: E) -> Self {
+        Self {
+            inner: Box::new(err),
+            backtrace: Backtrace::capture(),
+        }
+    }
+}
+
+type RejectFn = dyn Fn(Rejection, &Request) -> Response + std::marker::Send + std::marker::Sync;
+
+/// A rejection handler, set with [`Bison::on_rejection`](crate::Bison::on_rejection)
+/// or [`Scope::on_rejection`](crate::Scope::on_rejection).
+#[derive(Clone)]
+pub(crate) struct OnRejection(Arc<RejectFn>);
+
+impl OnRejection {
+    pub(crate) fn new<F>(f: F) -> Self
+    where
+        F: Fn(Rejection, &Request) -> Response + std::marker::Send + std::marker::Sync + 'static,
+    {
+        OnRejection(Arc::new(f))
+    }
+}
+
+#[crate::async_trait_internal]
+impl Wrap<Request> for OnRejection {
+    type Rejection = Infallible;
+
+    async fn call(&self, req: Request, next: &impl Next) -> Result<Response, Self::Rejection> {
+        match next.call(req.clone()).await {
+            Ok(response) => Ok(response),
+            Err(rejection) => Ok((self.0)(rejection, &req)),
+        }
+    }
+}
+
+/// Turn a rejection into a response, using the rejection handler
+/// of the application if one was set.
+pub(crate) fn handle(rejection: Rejection, req: &Request) -> Response {
+    match req.state::<OnRejection>() {
+        Some(on_rejection) => (on_rejection.0)(rejection, req),
+        None => rejection.reject(req),
+    }
+}
+
+/// A type that can be converted into a [`Rejection`].
+///
+/// This trait allows [`Rejection`] and [`Response`]
+/// to be used as rejections while not implementing
+/// [`Reject`] directly. You shouldn't have to
+/// worry about this trait, but it may show up
+/// in error messages when [`Reject`] is not
+/// implemented.
+pub trait IntoRejection: Send + Sync {
+    fn into_response_error(self) -> Rejection;
+}
+
+impl<E> IntoRejection for E
+where
+    E: Reject + 'static,
+{
+    fn into_response_error(self) -> Rejection {
+        self.into()
+    }
+}
+
+impl IntoRejection for Rejection {
+    fn into_response_error(self) -> Rejection {
+        self
+    }
+}
+
+impl IntoRejection for Response {
+    fn into_response_error(self) -> Rejection {
+        ResponseRejection(self).into()
+    }
+}
+
+/// A response used as a rejection, which is returned as is.
+struct ResponseRejection(Response);
+
+impl fmt::Debug for ResponseRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl fmt::Display for ResponseRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "status {:?}: {:?}", self.0.status(), self.0.body())
+    }
+}
+
+impl Reject for ResponseRejection {
+    fn status(&self) -> StatusCode {
+        self.0.status()
+    }
+
+    fn reject(self, _: &Request) -> Response {
+        self.0
+    }
+}
+
+/// The format of the response bodies produced by rejections.
+///
+/// The built-in rejections render their error message, along with the
+/// offending field or input location when known, in the format set with
+/// [`Bison::error_format`](crate::Bison::error_format). By default, they
+/// respond with an empty body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// An empty body.
+    #[default]
+    Empty,
+    /// A `text/plain` body containing the error message.
+    Plain,
+    /// An `application/json` body:
+    ///
+    /// ```json
+    /// { "status": 400, "message": "...", "field": "id", "line": 1, "column": 9 }
+    /// ```
+    Json,
+    /// An [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807) `application/problem+json` body:
+    ///
+    /// ```json
+    /// { "type": "about:blank", "title": "Bad Request", "status": 400, "detail": "...", "field": "id" }
+    /// ```
+    Problem,
+}
+
+/// An error response rendered in the configured [`ErrorFormat`].
+///
+/// This is used by the built-in rejections, and can be used to render
+/// custom rejections consistently.
+///
+/// ```
+/// use bison::http::StatusCode;
+/// use bison::reject::{ErrorResponse, Reject};
+/// use bison::{Request, Response};
+///
+/// #[derive(Debug)]
+/// struct UserNotFound(u64);
+///
+/// impl std::fmt::Display for UserNotFound {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "user {} does not exist", self.0)
+///     }
+/// }
+///
+/// impl Reject for UserNotFound {
+///     fn reject(self, req: &Request) -> Response {
+///         ErrorResponse::new(StatusCode::NOT_FOUND, self.to_string())
+///             .field("id")
+///             .into_response(req)
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ErrorResponse {
+    status: StatusCode,
+    message: String,
+    field: Option<String>,
+    location: Option<(usize, usize)>,
+}
+
+impl ErrorResponse {
+    /// Create an error response with the given status and message.
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        ErrorResponse {
+            status,
+            message: message.into(),
+            field: None,
+            location: None,
+        }
+    }
+
+    /// Set the field that caused the error.
+    pub fn field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// Set the location in the input that caused the error.
+    pub fn location(mut self, line: usize, column: usize) -> Self {
+        self.location = Some((line, column));
+        self
+    }
+
+    /// Render the response in the [`ErrorFormat`] configured for
+    /// the application.
+    pub fn into_response(self, req: &Request) -> Response {
+        let format = req.state::<ErrorFormat>().copied().unwrap_or_default();
+        self.render(format)
+    }
+
+    /// Render the response in the given format.
+    pub fn render(self, format: ErrorFormat) -> Response {
+        let builder = ResponseBuilder::new().status(self.status);
+
+        let (content_type, body) = match format {
+            ErrorFormat::Empty => return builder.body(Body::empty()).unwrap(),
+            ErrorFormat::Plain => ("text/plain; charset=utf-8", self.message),
+            ErrorFormat::Json => {
+                let mut body = format!(r#"{{"status":{},"message":"#, self.status.as_u16());
+                escape_json(&mut body, &self.message);
+                self.write_details(&mut body);
+                ("application/json", body)
+            }
+            ErrorFormat::Problem => {
+                let title = self.status.canonical_reason().unwrap_or("Unknown Error");
+                let mut body = String::from(r#"{"type":"about:blank","title":"#);
+                escape_json(&mut body, title);
+                let _ = write!(body, r#","status":{},"detail":"#, self.status.as_u16());
+                escape_json(&mut body, &self.message);
+                self.write_details(&mut body);
+                ("application/problem+json", body)
+            }
+        };
+
+        builder
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::once(body))
+            .unwrap()
+    }
+
+    fn write_details(&self, body: &mut String) {
+        if let Some(field) = &self.field {
+            body.push_str(r#","field":"#);
+            escape_json(body, field);
+        }
+
+        if let Some((line, column)) = self.location {
+            let _ = write!(body, r#","line":{},"column":{}"#, line, column);
+        }
+
+        body.push('}');
+    }
+}
+
+/// A response error that returns a 404 not found response.
+#[derive(Debug)]
+pub struct NotFound;
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "404 not found")
+    }
+}
+
+impl Reject for NotFound {
+    fn status(&self) -> StatusCode {
+        StatusCode::NOT_FOUND
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        ErrorResponse::new(self.status(), self.to_string()).into_response(req)
+    }
+}
+
+/// A response error that returns a 406 not acceptable response.
+///
+/// This is returned by [`Negotiate`](crate::respond::Negotiate) when none
+/// of the available representations are acceptable to the client.
+#[derive(Debug)]
+pub struct NotAcceptable;
+
+impl fmt::Display for NotAcceptable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "406 not acceptable")
+    }
+}
+
+impl Reject for NotAcceptable {
+    fn status(&self) -> StatusCode {
+        StatusCode::NOT_ACCEPTABLE
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        let mut response = ErrorResponse::new(self.status(), self.to_string()).into_response(req);
+
+        response
+            .headers_mut()
+            .insert(header::VARY, HeaderValue::from_static("accept"));
+
+        response
+    }
+}
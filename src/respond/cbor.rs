@@ -0,0 +1,35 @@
+use crate::http::{header, Body, Response, ResponseBuilder};
+use crate::reject::Rejection;
+use crate::Respond;
+
+use super::SerializeError;
+
+use serde::Serialize;
+
+/// Serializes a value as CBOR into the response body.
+///
+/// The response has a `Content-Type` of `application/cbor`. If the value
+/// fails to serialize, the request is rejected with a 500 response.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cbor<T>(pub T);
+
+impl<T> Respond for Cbor<T>
+where
+    T: Serialize,
+{
+    type Rejection = Rejection;
+
+    fn respond(self) -> Result<Response, Rejection> {
+        let mut body = Vec::new();
+
+        ciborium::ser::into_writer(&self.0, &mut body).map_err(|err| SerializeError {
+            format: "cbor",
+            err: err.into(),
+        })?;
+
+        Ok(ResponseBuilder::new()
+            .header(header::CONTENT_TYPE, "application/cbor")
+            .body(Body::once(body))
+            .unwrap())
+    }
+}
@@ -0,0 +1,64 @@
+use crate::http::{header, Body, Response, ResponseBuilder};
+use crate::reject::Rejection;
+use crate::Respond;
+
+use super::SerializeError;
+
+use serde::Serialize;
+
+/// Serializes a value as JSON into the response body.
+///
+/// The response has a `Content-Type` of `application/json`. If the value
+/// fails to serialize, the request is rejected with a 500 response.
+///
+/// # Examples
+///
+/// ```
+/// use bison::respond::Json;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// async fn user() -> Json<User> {
+///     Json(User {
+///         id: 1,
+///         name: "ferris".into(),
+///     })
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+impl<T> Respond for Json<T>
+where
+    T: Serialize,
+{
+    type Rejection = Rejection;
+
+    fn respond(self) -> Result<Response, Rejection> {
+        let body = serde_json::to_vec(&self.0).map_err(|err| SerializeError {
+            format: "json",
+            err: err.into(),
+        })?;
+
+        Ok(ResponseBuilder::new()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::once(body))
+            .unwrap())
+    }
+}
+
+/// Responds with the value as JSON.
+///
+/// See [`Json`] for details.
+impl Respond for serde_json::Value {
+    type Rejection = Rejection;
+
+    fn respond(self) -> Result<Response, Rejection> {
+        Json(self).respond()
+    }
+}
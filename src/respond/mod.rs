@@ -0,0 +1,375 @@
+//! Types that can be converted into HTTP responses.
+
+mod negotiate;
+pub use negotiate::Negotiate;
+
+mod sse;
+pub use sse::{Event, KeepAlive, Sse};
+
+mod text;
+pub use text::{Html, Text};
+
+crate::util::cfg_json! {
+    mod json;
+    pub use json::Json;
+}
+
+crate::util::cfg_msgpack! {
+    mod msgpack;
+    pub use msgpack::MsgPack;
+}
+
+crate::util::cfg_cbor! {
+    mod cbor;
+    pub use cbor::Cbor;
+}
+
+crate::util::cfg_protobuf! {
+    mod protobuf;
+    pub use protobuf::Protobuf;
+}
+
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::error::Error as StdError;
+
+use crate::bounded::Send;
+use crate::http::header::HeaderName;
+use crate::http::headers::{InvalidHeader, TypedHeader};
+use crate::http::{
+    header, Body, Bytes, HeaderMap, HeaderValue, Response, ResponseBuilder, StatusCode,
+};
+use crate::reject::{ErrorResponse, IntoRejection, NotFound, Reject, Rejection};
+use crate::Request;
+
+use std::fmt;
+
+/// A type that can be converted into an HTTP response.
+///
+/// This trait can be derived for structs and enums. A struct responds
+/// with its single field, or is serialized in the format given with
+/// `#[respond(json)]`, `#[respond(msgpack)]` or `#[respond(cbor)]`.
+/// Each variant of an enum responds with its field, or an empty body
+/// for unit variants. `#[respond(status = ...)]` sets the status code
+/// of the response.
+///
+/// ```
+/// use bison::Respond;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     id: u64,
+/// }
+///
+/// #[derive(Respond)]
+/// #[respond(json)]
+/// enum GetUser {
+///     #[respond(status = 200)]
+///     Found(User),
+///     #[respond(status = 404)]
+///     NotFound,
+/// }
+/// ```
+pub trait Respond {
+    /// An error that can occur during the conversion.
+    type Rejection: IntoRejection;
+
+    /// Convert into an HTTP response.
+    fn respond(self) -> Result<Response, Self::Rejection>;
+
+    /// Returns a new responder that adds the provided status
+    /// code to the response.
+    fn with_status(self, status: StatusCode) -> (StatusCode, Self)
+    where
+        Self: Sized,
+    {
+        (status, self)
+    }
+
+    /// Returns a new responder that adds the provided
+    /// [typed header](crate::http::headers) to the response.
+    fn with_header<H>(self, header: H) -> (H, Self)
+    where
+        H: TypedHeader,
+        Self: Sized,
+    {
+        (header, self)
+    }
+
+    /// Erase the type of this responder.
+    ///
+    /// See [`BoxResponder`] for details.
+    fn boxed(self) -> BoxResponder
+    where
+        Self: Sized + Send + 'static,
+    {
+        BoxResponder::new(self)
+    }
+}
+
+impl Respond for () {
+    type Rejection = Infallible;
+
+    fn respond(self) -> Result<Response, Infallible> {
+        Ok(Response::default())
+    }
+}
+
+impl Respond for Response {
+    type Rejection = Infallible;
+
+    fn respond(self) -> Result<Response, Infallible> {
+        Ok(self)
+    }
+}
+
+impl<T> Respond for (StatusCode, T)
+where
+    T: Respond,
+{
+    type Rejection = T::Rejection;
+
+    fn respond(self) -> Result<Response, T::Rejection> {
+        self.1.respond().map(|mut response| {
+            *response.status_mut() = self.0;
+            response
+        })
+    }
+}
+
+/// Adds the headers to the response, replacing any existing
+/// values with the same names.
+impl<T> Respond for (HeaderMap, T)
+where
+    T: Respond,
+{
+    type Rejection = T::Rejection;
+
+    fn respond(self) -> Result<Response, T::Rejection> {
+        self.1.respond().map(|mut response| {
+            response.headers_mut().extend(self.0);
+            response
+        })
+    }
+}
+
+/// Adds the headers to the response, replacing any existing
+/// values with the same names.
+impl<T, const N: usize> Respond for ([(HeaderName, HeaderValue); N], T)
+where
+    T: Respond,
+{
+    type Rejection = T::Rejection;
+
+    fn respond(self) -> Result<Response, T::Rejection> {
+        (HeaderMap::from_iter(self.0), self.1).respond()
+    }
+}
+
+impl<T> Respond for (StatusCode, HeaderMap, T)
+where
+    T: Respond,
+{
+    type Rejection = T::Rejection;
+
+    fn respond(self) -> Result<Response, T::Rejection> {
+        (self.0, (self.1, self.2)).respond()
+    }
+}
+
+impl<T, const N: usize> Respond for (StatusCode, [(HeaderName, HeaderValue); N], T)
+where
+    T: Respond,
+{
+    type Rejection = T::Rejection;
+
+    fn respond(self) -> Result<Response, T::Rejection> {
+        (self.0, (self.1, self.2)).respond()
+    }
+}
+
+/// A type-erased responder.
+///
+/// This allows a handler to return different responders from
+/// different branches.
+///
+/// ```
+/// use bison::respond::{BoxResponder, Html, Json};
+/// use bison::Respond;
+///
+/// async fn user(json: bool) -> BoxResponder {
+///     if json {
+///         Json(serde_json::json!({ "id": 1 })).boxed()
+///     } else {
+///         Html("<p>1</p>").boxed()
+///     }
+/// }
+/// ```
+pub struct BoxResponder {
+    inner: Box<dyn ErasedRespond>,
+}
+
+impl BoxResponder {
+    /// Erase the type of the given responder.
+    pub fn new<T>(responder: T) -> Self
+    where
+        T: Respond + Send + 'static,
+    {
+        BoxResponder {
+            inner: Box::new(responder),
+        }
+    }
+}
+
+impl fmt::Debug for BoxResponder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxResponder").finish_non_exhaustive()
+    }
+}
+
+impl Respond for BoxResponder {
+    type Rejection = Rejection;
+
+    fn respond(self) -> Result<Response, Rejection> {
+        self.inner.respond()
+    }
+}
+
+trait ErasedRespond: Send {
+    fn respond(self: Box<Self>) -> Result<Response, Rejection>;
+}
+
+impl<T> ErasedRespond for T
+where
+    T: Respond + Send,
+{
+    fn respond(self: Box<Self>) -> Result<Response, Rejection> {
+        Respond::respond(*self).map_err(Rejection::new)
+    }
+}
+
+/// Adds a [typed header](crate::http::headers) to the response,
+/// replacing any existing values.
+impl<H, T> Respond for (H, T)
+where
+    H: TypedHeader,
+    T: Respond,
+{
+    type Rejection = Rejection;
+
+    fn respond(self) -> Result<Response, Rejection> {
+        let value = self
+            .0
+            .encode()
+            .map_err(|err| EncodeError { name: H::NAME, err })?;
+
+        let mut response = self.1.respond().map_err(Rejection::new)?;
+        response.headers_mut().insert(H::NAME, value);
+        Ok(response)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct EncodeError {
+    pub(crate) name: header::HeaderName,
+    pub(crate) err: InvalidHeader,
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to encode header '{}': {}", self.name, self.err)
+    }
+}
+
+impl Reject for EncodeError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.err)
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        ErrorResponse::new(self.status(), self.to_string()).into_response(req)
+    }
+}
+
+/// An error that occured while serializing a response body.
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+#[derive(Debug)]
+pub(crate) struct SerializeError {
+    pub(crate) format: &'static str,
+    pub(crate) err: crate::bounded::BoxError,
+}
+
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to serialize {} response: {}",
+            self.format, self.err
+        )
+    }
+}
+
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+impl Reject for SerializeError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&*self.err)
+    }
+
+    fn reject(self, req: &Request) -> Response {
+        ErrorResponse::new(self.status(), self.to_string()).into_response(req)
+    }
+}
+
+impl<T, E> Respond for Result<T, E>
+where
+    T: Respond,
+    E: IntoRejection,
+{
+    type Rejection = Rejection;
+
+    fn respond(self) -> Result<Response, Rejection> {
+        self.map_err(Rejection::new)
+            .and_then(|ok| ok.respond().map_err(Rejection::new))
+    }
+}
+
+impl<T> Respond for Option<T>
+where
+    T: Respond,
+{
+    type Rejection = Rejection;
+
+    fn respond(self) -> Result<Response, Rejection> {
+        match self {
+            Some(responder) => responder.respond().map_err(Rejection::new),
+            None => Err(NotFound.into()),
+        }
+    }
+}
+
+macro_rules! with_content_type {
+    ($($ty:ty $(|$into:ident)? => $content_type:literal),* $(,)?) => { $(
+        impl Respond for $ty {
+            type Rejection = Infallible;
+
+            fn respond(self) -> Result<Response, Infallible> {
+                Ok(ResponseBuilder::new()
+                    .header(header::CONTENT_TYPE, $content_type)
+                    .body(Body::once(self $(.$into())?))
+                    .unwrap())
+            }
+        })*
+    }
+}
+
+with_content_type! {
+    Bytes => "application/octet-stream",
+    Vec<u8> => "application/octet-stream",
+    &'static [u8] => "application/octet-stream",
+    Cow<'static, [u8]> | into_owned => "application/octet-stream",
+    String => "text/plain",
+    &'static str => "text/plain",
+    Cow<'static, str> | into_owned => "text/plain",
+}
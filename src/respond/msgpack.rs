@@ -0,0 +1,34 @@
+use crate::http::{header, Body, Response, ResponseBuilder};
+use crate::reject::Rejection;
+use crate::Respond;
+
+use super::SerializeError;
+
+use serde::Serialize;
+
+/// Serializes a value as MessagePack into the response body.
+///
+/// Structs are serialized as maps, keeping their field names. The response
+/// has a `Content-Type` of `application/msgpack`. If the value fails to
+/// serialize, the request is rejected with a 500 response.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgPack<T>(pub T);
+
+impl<T> Respond for MsgPack<T>
+where
+    T: Serialize,
+{
+    type Rejection = Rejection;
+
+    fn respond(self) -> Result<Response, Rejection> {
+        let body = rmp_serde::to_vec_named(&self.0).map_err(|err| SerializeError {
+            format: "msgpack",
+            err: err.into(),
+        })?;
+
+        Ok(ResponseBuilder::new()
+            .header(header::CONTENT_TYPE, "application/msgpack")
+            .body(Body::once(body))
+            .unwrap())
+    }
+}
@@ -0,0 +1,159 @@
+use crate::extract::Negotiation;
+use crate::http::headers::Accept;
+use crate::http::{header, HeaderValue, Response};
+use crate::reject::{NotAcceptable, Rejection};
+use crate::Respond;
+
+use std::ptr;
+
+use mime::Mime;
+
+type Render<T> = Box<dyn FnOnce(&T) -> Result<Response, Rejection>>;
+
+/// A responder that picks one of several representations of the same
+/// value, based on the `Accept` header of the request.
+///
+/// Representations are tried in the order they were added when the client
+/// prefers them equally, and the first is used if the request has no
+/// `Accept` header. The `Content-Type` of the response is set to the media
+/// type of the chosen representation, and `Vary: accept` is added. If none
+/// of the representations are acceptable, the request is rejected with
+/// [`NotAcceptable`].
+///
+/// # Examples
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::{negotiation, Negotiation};
+/// use bison::respond::Negotiate;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Report {
+///     visits: u64,
+/// }
+///
+/// #[derive(Context)]
+/// struct GetReport {
+///     #[cx(negotiation)]
+///     negotiation: Negotiation,
+/// }
+///
+/// async fn report(cx: GetReport) -> Negotiate<Report> {
+///     Negotiate::new(&cx.negotiation, Report { visits: 42 })
+///         .json()
+///         .html(|report| format!("<p>{} visits</p>", report.visits))
+///         .csv(|report| format!("visits\n{}\n", report.visits))
+/// }
+/// ```
+pub struct Negotiate<T> {
+    accept: Option<Accept>,
+    value: T,
+    types: Vec<Mime>,
+    renderers: Vec<Render<T>>,
+}
+
+impl<T> Negotiate<T> {
+    /// Create a responder for the given value, without any representations.
+    pub fn new(negotiation: &Negotiation, value: T) -> Self {
+        Negotiate {
+            accept: negotiation.accept().cloned(),
+            value,
+            types: Vec::new(),
+            renderers: Vec::new(),
+        }
+    }
+
+    /// Add a representation with the given media type.
+    ///
+    /// `render` is only called if the representation is chosen.
+    pub fn with<F, R>(mut self, mime: Mime, render: F) -> Self
+    where
+        F: FnOnce(&T) -> R + 'static,
+        R: Respond,
+    {
+        self.types.push(mime);
+        self.renderers.push(Box::new(|value| {
+            render(value).respond().map_err(Rejection::new)
+        }));
+        self
+    }
+
+    /// Add a `text/html` representation.
+    pub fn html<F, R>(self, render: F) -> Self
+    where
+        F: FnOnce(&T) -> R + 'static,
+        R: Respond,
+    {
+        self.with(mime::TEXT_HTML_UTF_8, render)
+    }
+
+    /// Add a `text/csv` representation.
+    pub fn csv<F, R>(self, render: F) -> Self
+    where
+        F: FnOnce(&T) -> R + 'static,
+        R: Respond,
+    {
+        self.with(mime::TEXT_CSV_UTF_8, render)
+    }
+
+    /// Add an `application/json` representation, serializing the value
+    /// with `serde_json`.
+    #[cfg(feature = "json")]
+    pub fn json(self) -> Self
+    where
+        T: serde::Serialize,
+    {
+        self.with(mime::APPLICATION_JSON, |value| super::Json(value).respond())
+    }
+
+    /// Add an `application/msgpack` representation, serializing the value
+    /// with `rmp-serde`.
+    #[cfg(feature = "msgpack")]
+    pub fn msgpack(self) -> Self
+    where
+        T: serde::Serialize,
+    {
+        let mime = "application/msgpack".parse().unwrap();
+        self.with(mime, |value| super::MsgPack(value).respond())
+    }
+
+    /// Add an `application/cbor` representation, serializing the value
+    /// with `ciborium`.
+    #[cfg(feature = "cbor")]
+    pub fn cbor(self) -> Self
+    where
+        T: serde::Serialize,
+    {
+        let mime = "application/cbor".parse().unwrap();
+        self.with(mime, |value| super::Cbor(value).respond())
+    }
+}
+
+impl<T> Respond for Negotiate<T> {
+    type Rejection = Rejection;
+
+    fn respond(self) -> Result<Response, Rejection> {
+        let chosen = match &self.accept {
+            Some(accept) => accept.preferred(&self.types),
+            None => self.types.first(),
+        };
+
+        let index = chosen
+            .and_then(|chosen| self.types.iter().position(|mime| ptr::eq(mime, chosen)))
+            .ok_or(NotAcceptable)?;
+
+        let mime = &self.types[index];
+        let render = self.renderers.into_iter().nth(index).unwrap();
+        let mut response = render(&self.value)?;
+
+        let headers = response.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_str(mime.as_ref()).unwrap(),
+        );
+        headers.append(header::VARY, HeaderValue::from_static("accept"));
+
+        Ok(response)
+    }
+}
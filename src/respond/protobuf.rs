@@ -0,0 +1,26 @@
+use crate::http::{header, Body, Response, ResponseBuilder};
+use crate::Respond;
+
+use std::convert::Infallible;
+
+use prost::Message;
+
+/// Encodes a Protobuf message into the response body.
+///
+/// The response has a `Content-Type` of `application/protobuf`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Protobuf<T>(pub T);
+
+impl<T> Respond for Protobuf<T>
+where
+    T: Message,
+{
+    type Rejection = Infallible;
+
+    fn respond(self) -> Result<Response, Infallible> {
+        Ok(ResponseBuilder::new()
+            .header(header::CONTENT_TYPE, "application/protobuf")
+            .body(Body::once(self.0.encode_to_vec()))
+            .unwrap())
+    }
+}
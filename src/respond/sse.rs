@@ -0,0 +1,319 @@
+use crate::bounded::{Send, Sync};
+use crate::http::{header, Body, Bytes, Response, ResponseBuilder};
+use crate::Respond;
+
+use std::convert::Infallible;
+use std::error::Error as StdError;
+use std::fmt::Write;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio::time::Sleep;
+
+/// Responds with a stream of [Server-Sent Events].
+///
+/// The response has a `Content-Type` of `text/event-stream`. Each event
+/// is sent as soon as the stream yields it, and an error ends the
+/// response. Clients resuming a dropped connection send the ID of the
+/// last event they received, which can be extracted as a
+/// [`LastEventId`](crate::http::headers::LastEventId) header.
+///
+/// [Server-Sent Events]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+///
+/// # Examples
+///
+/// ```
+/// use bison::Context;
+/// use bison::extract::{typed_header, Optional};
+/// use bison::http::headers::LastEventId;
+/// use bison::respond::{Event, KeepAlive, Sse};
+/// use futures_core::Stream;
+/// use std::convert::Infallible;
+/// # use std::pin::Pin;
+/// # use std::task::{Context as TaskContext, Poll};
+///
+/// #[derive(Context)]
+/// struct Updates {
+///     #[cx(typed_header)]
+///     last_event_id: Optional<LastEventId>,
+/// }
+///
+/// async fn updates(cx: Updates) -> Sse<Feed> {
+///     let after = cx.last_event_id.into_inner();
+///     Sse::new(Feed::subscribe(after)).keep_alive(KeepAlive::new())
+/// }
+///
+/// /// A stream of dashboard updates.
+/// struct Feed {
+///     // ...
+/// }
+///
+/// impl Feed {
+///     /// Subscribe to updates after the given event.
+///     fn subscribe(after: Option<LastEventId>) -> Self {
+///         // ...
+///         # Feed {}
+///     }
+/// }
+///
+/// impl Stream for Feed {
+///     type Item = Result<Event, Infallible>;
+///
+///     // ...
+///     # fn poll_next(self: Pin<&mut Self>, _: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+///     #     Poll::Ready(Some(Ok(Event::new().id("1").event("update").data("{}"))))
+///     # }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Sse<S> {
+    events: S,
+    keep_alive: Option<KeepAlive>,
+}
+
+impl<S> Sse<S> {
+    /// Create a responder from a stream of events.
+    pub fn new(events: S) -> Self {
+        Sse {
+            events,
+            keep_alive: None,
+        }
+    }
+
+    /// Send keep-alive comments while the stream is idle.
+    ///
+    /// Proxies often close connections that have been quiet for too long.
+    /// By default no keep-alive comments are sent.
+    pub fn keep_alive(mut self, keep_alive: KeepAlive) -> Self {
+        self.keep_alive = Some(keep_alive);
+        self
+    }
+}
+
+impl<S, E> Respond for Sse<S>
+where
+    S: Stream<Item = Result<Event, E>> + Send + Sync + 'static,
+    E: StdError + Send + Sync + 'static,
+{
+    type Rejection = Infallible;
+
+    fn respond(self) -> Result<Response, Infallible> {
+        let stream = SseStream {
+            events: self.events,
+            keep_alive: self.keep_alive,
+            sleep: None,
+        };
+
+        Ok(ResponseBuilder::new()
+            .header(header::CONTENT_TYPE, "text/event-stream")
+            .header(header::CACHE_CONTROL, "no-cache")
+            .body(Body::stream(stream))
+            .unwrap())
+    }
+}
+
+pin_project_lite::pin_project! {
+    struct SseStream<S> {
+        #[pin]
+        events: S,
+        keep_alive: Option<KeepAlive>,
+        sleep: Option<Pin<Box<Sleep>>>,
+    }
+}
+
+impl<S, E> Stream for SseStream<S>
+where
+    S: Stream<Item = Result<Event, E>>,
+{
+    type Item = Result<Bytes, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        match this.events.poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => {
+                // the keep-alive interval restarts after every event
+                *this.sleep = None;
+                Poll::Ready(Some(Ok(event.encode())))
+            }
+            Poll::Ready(Some(Err(err))) => Poll::Ready(Some(Err(err))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => {
+                if let Some(keep_alive) = this.keep_alive {
+                    let sleep = this
+                        .sleep
+                        .get_or_insert_with(|| Box::pin(tokio::time::sleep(keep_alive.interval)));
+
+                    if sleep.as_mut().poll(cx).is_ready() {
+                        *this.sleep = None;
+                        return Poll::Ready(Some(Ok(keep_alive.comment.clone())));
+                    }
+                }
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// An event sent by an [`Sse`] responder.
+///
+/// # Panics
+///
+/// Event names, IDs and comments cannot contain newlines, and IDs
+/// cannot contain null characters. The setters panic if they do.
+#[derive(Debug, Clone, Default)]
+pub struct Event {
+    id: Option<String>,
+    event: Option<String>,
+    data: Option<String>,
+    retry: Option<Duration>,
+    comment: Option<String>,
+}
+
+impl Event {
+    /// Create an empty event.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the ID of the event.
+    ///
+    /// Clients send the ID of the last event they received when
+    /// reconnecting, in the `Last-Event-ID` header.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        let id = id.into();
+        assert!(
+            !id.contains(['\r', '\n', '\0']),
+            "event IDs cannot contain newlines or null characters"
+        );
+        self.id = Some(id);
+        self
+    }
+
+    /// Set the name of the event.
+    ///
+    /// Clients dispatch unnamed events as `message` events.
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        let event = event.into();
+        assert!(
+            !event.contains(['\r', '\n']),
+            "event names cannot contain newlines"
+        );
+        self.event = Some(event);
+        self
+    }
+
+    /// Set the data of the event.
+    ///
+    /// Data with multiple lines is sent as multiple `data` fields,
+    /// which the client joins back together.
+    pub fn data(mut self, data: impl Into<String>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Set the data of the event to the value serialized as JSON.
+    #[cfg(feature = "json")]
+    pub fn json_data<T>(self, value: &T) -> Result<Self, serde_json::Error>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        Ok(self.data(serde_json::to_string(value)?))
+    }
+
+    /// Set how long clients wait before reconnecting after the
+    /// connection is lost, in whole milliseconds.
+    pub fn retry(mut self, retry: Duration) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Add a comment to the event, which clients ignore.
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        let comment = comment.into();
+        assert!(
+            !comment.contains(['\r', '\n']),
+            "comments cannot contain newlines"
+        );
+        self.comment = Some(comment);
+        self
+    }
+
+    fn encode(&self) -> Bytes {
+        let mut out = String::new();
+
+        if let Some(comment) = &self.comment {
+            let _ = writeln!(out, ":{}", comment);
+        }
+
+        if let Some(event) = &self.event {
+            let _ = writeln!(out, "event:{}", event);
+        }
+
+        if let Some(data) = &self.data {
+            for line in data.split("\r\n").flat_map(|line| line.split(['\r', '\n'])) {
+                let _ = writeln!(out, "data:{}", line);
+            }
+        }
+
+        if let Some(id) = &self.id {
+            let _ = writeln!(out, "id:{}", id);
+        }
+
+        if let Some(retry) = self.retry {
+            let _ = writeln!(out, "retry:{}", retry.as_millis());
+        }
+
+        out.push('\n');
+        out.into()
+    }
+}
+
+/// Keep-alive comments sent by an [`Sse`] responder while the stream is idle.
+///
+/// See [`Sse::keep_alive`] for details.
+#[derive(Debug, Clone)]
+pub struct KeepAlive {
+    interval: Duration,
+    comment: Bytes,
+}
+
+impl KeepAlive {
+    /// Send an empty comment every 15 seconds.
+    pub fn new() -> Self {
+        KeepAlive {
+            interval: Duration::from_secs(15),
+            comment: Bytes::from_static(b":\n\n"),
+        }
+    }
+
+    /// Set how long the stream can be idle before a comment is sent.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the text of the keep-alive comment.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the text contains newlines.
+    pub fn text(mut self, text: &str) -> Self {
+        assert!(
+            !text.contains(['\r', '\n']),
+            "comments cannot contain newlines"
+        );
+        self.comment = format!(":{}\n\n", text).into();
+        self
+    }
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
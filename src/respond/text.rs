@@ -0,0 +1,64 @@
+use crate::http::{header, Body, Bytes, Response, ResponseBuilder};
+use crate::Respond;
+
+use std::convert::Infallible;
+
+/// Responds with an HTML body.
+///
+/// The response has a `Content-Type` of `text/html; charset=utf-8`.
+///
+/// # Examples
+///
+/// ```
+/// use bison::respond::Html;
+///
+/// async fn home() -> Html<&'static str> {
+///     Html("<h1>Hello!</h1>")
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Html<T>(pub T);
+
+impl<T> Respond for Html<T>
+where
+    T: Into<Bytes>,
+{
+    type Rejection = Infallible;
+
+    fn respond(self) -> Result<Response, Infallible> {
+        Ok(ResponseBuilder::new()
+            .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(Body::once(self.0))
+            .unwrap())
+    }
+}
+
+/// Responds with a plain text body.
+///
+/// The response has a `Content-Type` of `text/plain; charset=utf-8`.
+///
+/// # Examples
+///
+/// ```
+/// use bison::respond::Text;
+///
+/// async fn hello() -> Text<String> {
+///     Text(format!("Hello {}!", "world"))
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Text<T>(pub T);
+
+impl<T> Respond for Text<T>
+where
+    T: Into<Bytes>,
+{
+    type Rejection = Infallible;
+
+    fn respond(self) -> Result<Response, Infallible> {
+        Ok(ResponseBuilder::new()
+            .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body(Body::once(self.0))
+            .unwrap())
+    }
+}
@@ -2,19 +2,26 @@ mod scope;
 pub use scope::Scope;
 
 use crate::http::{self, header, Body, Method, Request, Response, ResponseBuilder, StatusCode};
-use crate::reject::IntoRejection;
+use crate::reject::{self, HttpError, IntoRejection, NotFound};
 use crate::state::AppState;
-use crate::wrap::{Call, Wrap};
-use crate::{handler, Context, Respond};
+use crate::wrap::{Call, CatchPanic, Wrap};
+use crate::{handler, Context, Rejection, Respond};
 
 use std::collections::HashMap;
 
+use crate::bounded::Rc;
+
 use ::http::Method as HttpMethod;
 use matchit::Node;
 
 pub struct Router<W> {
     wrap: W,
-    routes: HashMap<HttpMethod, Node<Box<handler::Erased>>>,
+    routes: HashMap<HttpMethod, Node<Route>>,
+}
+
+struct Route {
+    path: Rc<str>,
+    handler: Box<handler::Erased>,
 }
 
 impl Router<Call> {
@@ -47,10 +54,16 @@ where
         path: impl Into<String>,
         handler: Box<handler::Erased>,
     ) -> Result<Self, matchit::InsertError> {
+        let path = path.into();
+        let route = Route {
+            path: Rc::from(path.as_str()),
+            handler,
+        };
+
         self.routes
             .entry(method.into_http())
             .or_default()
-            .insert(path, handler)?;
+            .insert(path, route)?;
 
         Ok(self)
     }
@@ -89,12 +102,24 @@ where
         allowed
     }
 
+    async fn call(&self, req: Request, route: &Route) -> Result<Response, Rejection> {
+        let response = self
+            .wrap
+            .call(req, &route.handler)
+            .await
+            .map_err(IntoRejection::into_response_error)?;
+
+        response
+            .respond()
+            .map_err(IntoRejection::into_response_error)
+    }
+
     pub(crate) async fn serve(&self, req: ::http::Request<Body>, state: AppState) -> Response {
         let path = req.uri().path();
         match self.routes.get(req.method()) {
             Some(node) => match node.at(path) {
                 Ok(matched) => {
-                    let handler = matched.value;
+                    let route = matched.value;
 
                     let params = matched
                         .params
@@ -102,7 +127,7 @@ where
                         .map(|(k, v)| (k.to_owned(), v.to_owned()))
                         .collect::<http::request::Params>();
 
-                    let req = match Request::new(req, state, params) {
+                    let req = match Request::new(req, state, Some(route.path.clone()), params) {
                         Some(req) => req,
                         None => {
                             return ResponseBuilder::new()
@@ -112,12 +137,16 @@ where
                         }
                     };
 
-                    match self.wrap.call(req.clone(), handler).await {
-                        Ok(ok) => match ok.respond() {
-                            Ok(ok) => ok,
-                            Err(err) => err.into_response_error().reject(&req),
-                        },
-                        Err(err) => err.into_response_error().reject(&req),
+                    let response = self.call(req.clone(), route);
+
+                    let response = match req.state::<CatchPanic>() {
+                        Some(catch_panic) => catch_panic.catch(&req, Box::pin(response)).await,
+                        None => response.await,
+                    };
+
+                    match response {
+                        Ok(response) => response,
+                        Err(err) => reject::handle(err, &req),
                     }
                 }
                 Err(e) if e.tsr() && req.method() != HttpMethod::CONNECT && path != "/" => {
@@ -132,26 +161,39 @@ where
                         .body(Body::empty())
                         .unwrap()
                 }
-                Err(_) => ResponseBuilder::new()
-                    .status(StatusCode::NOT_FOUND)
-                    .body(Body::empty())
-                    .unwrap(),
+                Err(_) => not_found(req, state),
             },
             None => {
                 let allowed = self.allowed_methods(path);
                 if !allowed.is_empty() {
-                    ResponseBuilder::new()
-                        .header(header::ALLOW, allowed.join(", "))
-                        .status(StatusCode::METHOD_NOT_ALLOWED)
-                        .body(Body::empty())
-                        .unwrap()
+                    method_not_allowed(req, state, &allowed)
                 } else {
-                    ResponseBuilder::new()
-                        .status(StatusCode::NOT_FOUND)
-                        .body(Body::empty())
-                        .unwrap()
+                    not_found(req, state)
                 }
             }
         }
     }
 }
+
+/// Reject a request to a route that does not allow its method.
+fn method_not_allowed(req: ::http::Request<Body>, state: AppState, allowed: &[&str]) -> Response {
+    match Request::new(req, state, None, http::request::Params::default()) {
+        Some(req) => reject::handle(HttpError::method_not_allowed(allowed).into(), &req),
+        None => ResponseBuilder::new()
+            .header(header::ALLOW, allowed.join(", "))
+            .status(StatusCode::METHOD_NOT_ALLOWED)
+            .body(Body::empty())
+            .unwrap(),
+    }
+}
+
+/// Reject a request that did not match any route with [`NotFound`].
+fn not_found(req: ::http::Request<Body>, state: AppState) -> Response {
+    match Request::new(req, state, None, http::request::Params::default()) {
+        Some(req) => reject::handle(NotFound.into(), &req),
+        None => ResponseBuilder::new()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .unwrap(),
+    }
+}
@@ -1,8 +1,9 @@
 use crate::bounded::Rc;
 use crate::handler::{self, Context, Erased, Handler};
-use crate::http::Method;
+use crate::http::{Method, Response};
+use crate::reject::OnRejection;
 use crate::wrap::{Call, Wrap};
-use crate::{Bison, Request};
+use crate::{Bison, Rejection, Request};
 
 /// Routes scoped under a common prefix.
 ///
@@ -11,6 +12,7 @@ pub struct Scope<W> {
     wrap: W,
     prefix: String,
     routes: Vec<(Method, String, Box<Erased>)>,
+    on_rejection: Option<OnRejection>,
 }
 
 impl Scope<Call> {
@@ -24,6 +26,7 @@ impl Scope<Call> {
             wrap: Call::new(),
             prefix: prefix.into(),
             routes: Vec::new(),
+            on_rejection: None,
         }
     }
 }
@@ -38,14 +41,17 @@ where
     {
         let wrap = Rc::new(self.wrap);
         for (method, path, handler) in self.routes {
+            let handler: Box<Erased> = match &self.on_rejection {
+                Some(on_rejection) => {
+                    Box::new(handler.wrap(wrap.clone()).wrap(on_rejection.clone()))
+                }
+                None => Box::new(handler.wrap(wrap.clone())),
+            };
+
             bison = Bison {
                 router: bison
                     .router
-                    .route(
-                        method,
-                        format!("{}{}", self.prefix, path),
-                        Box::new(handler.wrap(wrap.clone())),
-                    )
+                    .route(method, format!("{}{}", self.prefix, path), handler)
                     .expect("failed to insert route"),
                 state: bison.state,
             };
@@ -63,8 +69,22 @@ where
             wrap: self.wrap.wrap(wrap),
             prefix: self.prefix,
             routes: self.routes,
+            on_rejection: self.on_rejection,
         }
     }
+
+    /// Set a handler that turns rejections from the routes in this
+    /// scope into responses.
+    ///
+    /// This takes precedence over [`Bison::on_rejection`], and applies
+    /// to rejections from the middleware of the scope as well.
+    pub fn on_rejection<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Rejection, &Request) -> Response + Send + Sync + 'static,
+    {
+        self.on_rejection = Some(OnRejection::new(f));
+        self
+    }
 }
 
 macro_rules! route {
@@ -1,3 +1,4 @@
+use std::fmt::Write;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -31,6 +32,69 @@ macro_rules! cfg_json {
     )*}
 }
 
+macro_rules! cfg_msgpack {
+    ($($x:item)*) => {$(
+        #[cfg(feature = "msgpack")]
+        $x
+    )*}
+}
+
+macro_rules! cfg_cbor {
+    ($($x:item)*) => {$(
+        #[cfg(feature = "cbor")]
+        $x
+    )*}
+}
+
+macro_rules! cfg_protobuf {
+    ($($x:item)*) => {$(
+        #[cfg(feature = "protobuf")]
+        $x
+    )*}
+}
+
+macro_rules! cfg_decompression {
+    ($($x:item)*) => {$(
+        #[cfg(feature = "decompression")]
+        $x
+    )*}
+}
+
+macro_rules! cfg_not_decompression {
+    ($($x:item)*) => {$(
+        #[cfg(not(feature = "decompression"))]
+        $x
+    )*}
+}
+
+macro_rules! cfg_cookies {
+    ($($x:item)*) => {$(
+        #[cfg(feature = "cookies")]
+        $x
+    )*}
+}
+
+macro_rules! cfg_multipart {
+    ($($x:item)*) => {$(
+        #[cfg(all(feature = "multipart", not(feature = "not-send")))]
+        $x
+    )*}
+}
+
+macro_rules! cfg_regex {
+    ($($x:item)*) => {$(
+        #[cfg(feature = "regex")]
+        $x
+    )*}
+}
+
+macro_rules! cfg_websocket {
+    ($($x:item)*) => {$(
+        #[cfg(feature = "websocket")]
+        $x
+    )*}
+}
+
 macro_rules! doc_inline {
     ($($x:item)*) => {$(
         #[doc(inline)]
@@ -38,7 +102,10 @@ macro_rules! doc_inline {
     )*}
 }
 
-pub(crate) use {cfg_json, doc_inline};
+pub(crate) use {
+    cfg_cbor, cfg_cookies, cfg_decompression, cfg_json, cfg_msgpack, cfg_multipart,
+    cfg_not_decompression, cfg_protobuf, cfg_regex, cfg_websocket, doc_inline,
+};
 
 macro_rules! _try {
     ($expr:expr) => {{
@@ -47,3 +114,24 @@ macro_rules! _try {
 }
 
 pub(crate) use _try;
+
+/// Appends `value` to `out` as a quoted JSON string.
+pub(crate) fn escape_json(out: &mut String, value: &str) {
+    out.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
@@ -0,0 +1,443 @@
+//! Validation of extracted values.
+//!
+//! Types implementing [`Validate`] can be validated after extraction by
+//! adding `validate` to the extractor attribute of a [`Context`] field.
+//! Validation failures reject the request with a [`ValidationRejection`].
+//!
+//! [`Validate`] is usually implemented with its derive macro, which
+//! supports the following rules:
+//!
+//! - `length(min = .., max = .., equal = ..)`: the length of a string, in
+//!   characters, or of a collection, see [`Length`].
+//! - `range(min = .., max = ..)`: the value of a number, or any type
+//!   implementing `PartialOrd`.
+//! - `regex = ".."`: a string matching a regular expression. Requires the
+//!   `regex` feature. A path to a static `Regex` can be used instead of a
+//!   literal.
+//! - `email`: a string that looks like an email address.
+//! - `nested`: a field that implements [`Validate`] itself.
+//!
+//! Every rule accepts an optional `message` overriding the default error
+//! message. Fields of type `Option<T>` are only validated if present.
+//!
+//! # Examples
+//!
+//! ```
+//! use bison::extract::json;
+//! use bison::{Context, Validate};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, Validate)]
+//! struct SignUp {
+//!     #[validate(length(min = 3, max = 20))]
+//!     username: String,
+//!     #[validate(email(message = "please enter a valid email"))]
+//!     email: String,
+//!     #[validate(range(min = 13))]
+//!     age: Option<u8>,
+//! }
+//!
+//! #[derive(Context)]
+//! struct Register {
+//!     #[cx(json, validate)]
+//!     form: SignUp,
+//! }
+//! ```
+//!
+//! [`Context`]: crate::Context
+
+use crate::http::{header, Body, Request, Response, ResponseBuilder, StatusCode};
+use crate::util::escape_json;
+use crate::Reject;
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+
+/// A type that can be validated.
+///
+/// See the [module level documentation](self) for details.
+pub trait Validate {
+    /// Validate the value, returning all errors that were found.
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+impl<T> Validate for Option<T>
+where
+    T: Validate,
+{
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            Some(value) => value.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> Validate for Vec<T>
+where
+    T: Validate,
+{
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        for (i, value) in self.iter().enumerate() {
+            if let Err(nested) = value.validate() {
+                errors.merge(format!("[{}]", i), nested);
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+impl<T> Validate for Box<T>
+where
+    T: Validate + ?Sized,
+{
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        (**self).validate()
+    }
+}
+
+/// Validate an extracted value.
+///
+/// This is called by the [`Context`](crate::Context) derive for fields
+/// marked with `validate`.
+pub fn validated<T>(value: T) -> Result<T, ValidationRejection>
+where
+    T: Validate,
+{
+    match value.validate() {
+        Ok(()) => Ok(value),
+        Err(errors) => Err(ValidationRejection(errors)),
+    }
+}
+
+/// A list of validation errors.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationErrors(Vec<FieldError>);
+
+/// A validation error for a single field.
+#[derive(Debug, Clone)]
+pub struct FieldError {
+    field: String,
+    code: Cow<'static, str>,
+    message: Cow<'static, str>,
+}
+
+impl FieldError {
+    /// Returns the path of the field, such as `address.city` for nested fields.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the name of the rule that failed, such as `length`.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns the error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl ValidationErrors {
+    /// Create an empty list of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an error for the given field.
+    pub fn add(
+        &mut self,
+        field: impl Into<String>,
+        code: impl Into<Cow<'static, str>>,
+        message: impl Into<Cow<'static, str>>,
+    ) {
+        self.0.push(FieldError {
+            field: field.into(),
+            code: code.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Add the errors of a nested value, prefixing their field paths.
+    pub fn merge(&mut self, prefix: impl Into<String>, nested: ValidationErrors) {
+        let prefix = prefix.into();
+
+        for mut error in nested.0 {
+            error.field = match error.field.as_str() {
+                "" => prefix.clone(),
+                field if field.starts_with('[') => format!("{}{}", prefix, field),
+                field => format!("{}.{}", prefix, field),
+            };
+
+            self.0.push(error);
+        }
+    }
+
+    /// Returns `true` if there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the errors.
+    pub fn iter(&self) -> impl Iterator<Item = &FieldError> {
+        self.0.iter()
+    }
+
+    /// Returns the errors for the given field.
+    pub fn field<'a>(&'a self, field: &'a str) -> impl Iterator<Item = &'a FieldError> {
+        self.0.iter().filter(move |error| error.field == field)
+    }
+
+    /// Returns `Ok` if there are no errors, or `Err(self)` otherwise.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "`{}` {}", error.field, error.message)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// The error returned when an extracted value fails validation.
+///
+/// Returns a 422 response with a JSON body listing the errors:
+///
+/// ```json
+/// {
+///   "errors": [
+///     { "field": "username", "code": "length", "message": "length must be at least 3" }
+///   ]
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ValidationRejection(ValidationErrors);
+
+impl ValidationRejection {
+    /// Returns the validation errors.
+    pub fn errors(&self) -> &ValidationErrors {
+        &self.0
+    }
+}
+
+impl From<ValidationErrors> for ValidationRejection {
+    fn from(errors: ValidationErrors) -> Self {
+        ValidationRejection(errors)
+    }
+}
+
+impl fmt::Display for ValidationRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "validation failed: {}", self.0)
+    }
+}
+
+impl Reject for ValidationRejection {
+    fn status(&self) -> StatusCode {
+        StatusCode::UNPROCESSABLE_ENTITY
+    }
+
+    fn reject(self, _: &Request) -> Response {
+        let mut body = String::from(r#"{"errors":["#);
+
+        for (i, error) in self.0.iter().enumerate() {
+            if i != 0 {
+                body.push(',');
+            }
+
+            body.push_str(r#"{"field":"#);
+            escape_json(&mut body, &error.field);
+            body.push_str(r#","code":"#);
+            escape_json(&mut body, &error.code);
+            body.push_str(r#","message":"#);
+            escape_json(&mut body, &error.message);
+            body.push('}');
+        }
+
+        body.push_str("]}");
+
+        ResponseBuilder::new()
+            .status(self.status())
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::once(body))
+            .unwrap()
+    }
+}
+
+/// A type with a length that can be validated.
+///
+/// The length of a string is the number of characters it contains,
+/// and the length of a collection is the number of elements.
+pub trait Length {
+    /// Returns the length of the value.
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl Length for Cow<'_, str> {
+    fn length(&self) -> usize {
+        self.as_ref().length()
+    }
+}
+
+impl<T: Length + ?Sized> Length for &T {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+macro_rules! collection_length {
+    ($($ty:ident<$($param:ident),*>),* $(,)?) => ($(
+        impl<$($param),*> Length for $ty<$($param),*> {
+            fn length(&self) -> usize {
+                self.len()
+            }
+        }
+    )*)
+}
+
+collection_length! {
+    Vec<T>, VecDeque<T>, HashSet<T, S>, BTreeSet<T>, HashMap<K, V, S>, BTreeMap<K, V>,
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// The validation rules supported by the [`Validate`] derive.
+///
+/// These can also be used when implementing [`Validate`] by hand.
+/// Each rule returns the default error message on failure.
+pub mod rules {
+    use super::Length;
+
+    use std::fmt::Display;
+
+    /// Validate the [`Length`] of a value.
+    pub fn length<T>(
+        value: &T,
+        min: Option<usize>,
+        max: Option<usize>,
+        equal: Option<usize>,
+    ) -> Result<(), String>
+    where
+        T: Length + ?Sized,
+    {
+        let len = value.length();
+
+        match (min, max, equal) {
+            (_, _, Some(equal)) if len != equal => Err(format!("length must be {}", equal)),
+            (Some(min), Some(max), _) if len < min || len > max => {
+                Err(format!("length must be between {} and {}", min, max))
+            }
+            (Some(min), _, _) if len < min => Err(format!("length must be at least {}", min)),
+            (_, Some(max), _) if len > max => Err(format!("length must be at most {}", max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate that a value is within a range.
+    pub fn range<T>(value: &T, min: Option<T>, max: Option<T>) -> Result<(), String>
+    where
+        T: PartialOrd + Display,
+    {
+        match (min, max) {
+            (Some(min), Some(max)) if *value < min || *value > max => {
+                Err(format!("must be between {} and {}", min, max))
+            }
+            (Some(min), _) if *value < min => Err(format!("must be at least {}", min)),
+            (_, Some(max)) if *value > max => Err(format!("must be at most {}", max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate that a string looks like an email address.
+    ///
+    /// This is intentionally lenient: the address must have a non-empty
+    /// local part and a domain with at least two labels, and may not
+    /// contain whitespace.
+    pub fn email<T>(value: &T) -> Result<(), String>
+    where
+        T: AsRef<str> + ?Sized,
+    {
+        let value = value.as_ref();
+
+        let valid = match value.rsplit_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && local.len() <= 64
+                    && !value.chars().any(char::is_whitespace)
+                    && domain.contains('.')
+                    && domain.split('.').all(|label| {
+                        !label.is_empty()
+                            && !label.starts_with('-')
+                            && !label.ends_with('-')
+                            && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+                    })
+            }
+            None => false,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err("must be a valid email address".to_owned())
+        }
+    }
+
+    crate::util::cfg_regex! {
+        /// Validate that a string matches a regular expression.
+        pub fn regex<T>(value: &T, regex: &::regex::Regex) -> Result<(), String>
+        where
+            T: AsRef<str> + ?Sized,
+        {
+            if regex.is_match(value.as_ref()) {
+                Ok(())
+            } else {
+                Err(format!("must match the pattern `{}`", regex.as_str()))
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use once_cell::sync::Lazy;
+
+    crate::util::cfg_regex! {
+        pub use ::regex::Regex;
+    }
+}
@@ -0,0 +1,127 @@
+use crate::bounded::BoxFuture;
+use crate::http::{Request, Response};
+use crate::reject::{ErrorResponse, Reject};
+use crate::wrap::{Next, Wrap};
+use crate::Rejection;
+
+use std::any::Any;
+use std::fmt;
+use std::future::poll_fn;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::task::Poll;
+
+type OnPanic = dyn Fn(&Panicked, &Request) + std::marker::Send + std::marker::Sync;
+
+/// Middleware that catches panics and rejects the request with [`Panicked`].
+///
+/// The panic message can be reported with [`on_panic`](CatchPanic::on_panic).
+/// Applied with [`Bison::catch_panic`](crate::Bison::catch_panic), panics in
+/// extractors, middleware, handlers and responders are all caught. As
+/// regular middleware, it only catches panics from the rest of the chain.
+///
+/// ```
+/// use bison::Bison;
+/// use bison::wrap::CatchPanic;
+///
+/// let bison = Bison::new().catch_panic(CatchPanic::new().on_panic(|panicked, req| {
+///     eprintln!("{} panicked: {}", req.uri(), panicked.message());
+/// }));
+/// ```
+#[derive(Clone, Default)]
+pub struct CatchPanic {
+    on_panic: Option<Arc<OnPanic>>,
+}
+
+impl CatchPanic {
+    /// Create a new instance of this middleware.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call the given function whenever a panic is caught.
+    pub fn on_panic<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Panicked, &Request) + std::marker::Send + std::marker::Sync + 'static,
+    {
+        self.on_panic = Some(Arc::new(f));
+        self
+    }
+
+    /// Run the future, rejecting the request if it panics.
+    pub(crate) async fn catch(
+        &self,
+        req: &Request,
+        mut future: BoxFuture<'_, Result<Response, Rejection>>,
+    ) -> Result<Response, Rejection> {
+        let result = poll_fn(|cx| {
+            match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+                Ok(poll) => poll.map(Ok),
+                Err(payload) => Poll::Ready(Err(payload)),
+            }
+        })
+        .await;
+
+        match result {
+            Ok(result) => result,
+            Err(payload) => {
+                let panicked = Panicked::new(payload);
+
+                if let Some(on_panic) = &self.on_panic {
+                    on_panic(&panicked, req);
+                }
+
+                Err(panicked.into())
+            }
+        }
+    }
+}
+
+#[crate::async_trait_internal]
+impl Wrap<Request> for CatchPanic {
+    type Rejection = Rejection;
+
+    async fn call(&self, req: Request, next: &impl Next) -> Result<Response, Self::Rejection> {
+        self.catch(&req, next.call(req.clone())).await
+    }
+}
+
+/// The rejection returned by [`CatchPanic`] when a panic is caught.
+///
+/// Returns a 500 response when used as a rejection.
+#[derive(Debug)]
+pub struct Panicked {
+    message: String,
+}
+
+impl Panicked {
+    fn new(payload: Box<dyn Any + std::marker::Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "Box<dyn Any>".to_owned(),
+            },
+        };
+
+        Panicked { message }
+    }
+
+    /// Returns the panic message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Panicked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "handler panicked: {}", self.message)
+    }
+}
+
+impl Reject for Panicked {
+    fn reject(self, req: &Request) -> Response {
+        // the panic message may contain sensitive information
+        ErrorResponse::new(self.status(), "internal server error").into_response(req)
+    }
+}
@@ -1,7 +1,10 @@
 //! Asynchronous middleware.
 
+mod catch_panic;
 mod wrap_fn;
 
+pub use catch_panic::{CatchPanic, Panicked};
+
 use std::marker::PhantomData;
 
 #[doc(hidden)]
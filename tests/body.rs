@@ -0,0 +1,267 @@
+use std::time::Duration;
+
+use bison::extract::{body, body_stream, BodyConfig, BodyRejection, BodyStream};
+use bison::http::{Body, HeaderMap, HeaderValue, StatusCode};
+use bison::{wrap_fn, Bison, Context, Rejection, Request};
+use bison_hyper::Serve;
+
+#[derive(Context)]
+struct Echo {
+    #[cx(body)]
+    body: String,
+}
+
+#[tokio::test]
+async fn buffer_replay() {
+    let bison = Bison::new()
+        .post("/", |cx: Echo| async move { cx.body })
+        .wrap(wrap_fn!(async |req, next| {
+            let bytes = req.body().buffer(16).await.map_err(Rejection::from)?;
+            assert_eq!(bytes, "hello");
+            assert!(req.body().try_clone().is_some());
+            next.call(req).await
+        }));
+
+    let chunks = vec![Ok::<_, std::io::Error>("hel".into()), Ok("lo".into())];
+
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .body(Body::stream(Iter(chunks.into_iter())))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body().buffer(16).await.unwrap(), "hello");
+}
+
+#[tokio::test]
+async fn buffer_overflow() {
+    let body = Body::once("hello world");
+    let err = body.buffer(5).await.unwrap_err();
+    assert!(err.is_overflow());
+
+    // the body is left intact
+    assert_eq!(body.buffer(64).await.unwrap(), "hello world");
+}
+
+#[tokio::test]
+async fn trailers() {
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", HeaderValue::from_static("0"));
+
+    let chunks = vec![Ok::<_, std::io::Error>("hello".into())];
+    let body = Body::stream_with_trailers(Iter(chunks.into_iter()), {
+        let trailers = trailers.clone();
+        async move { Ok(Some(trailers)) }
+    });
+
+    assert_eq!(body.buffer(16).await.unwrap(), "hello");
+    assert_eq!(body.trailers().await.unwrap(), Some(trailers.clone()));
+
+    let clone = body.try_clone().unwrap();
+    assert_eq!(clone.trailers().await.unwrap(), Some(trailers));
+
+    let chunks = vec![Ok::<_, std::io::Error>("hello".into())];
+    let body = Body::stream_with_trailers(Iter(chunks.into_iter()), async {
+        Err(std::io::Error::other("reset"))
+    });
+
+    assert!(body.trailers().await.is_err());
+    assert!(body.buffer(16).await.is_err());
+    assert_eq!(body.trailers().await.unwrap_err().to_string(), "reset");
+}
+
+#[tokio::test]
+async fn trailers_loopback() {
+    async fn handler(req: Request) -> String {
+        // the trailers cannot be read before the body
+        let early = req.body().trailers().await.unwrap_err();
+        let body = req.body().buffer(64).await.unwrap();
+        let trailers = req.body().trailers().await.unwrap().unwrap();
+
+        format!("{} {:?} {:?}", early, body, trailers["grpc-status"])
+    }
+
+    let server = Bison::new().post("/", handler).serve("127.0.0.1:0");
+    let addr = server.local_addr();
+    tokio::spawn(server);
+
+    let (mut sender, body) = hyper::Body::channel();
+    tokio::spawn(async move {
+        sender.send_data("hel".into()).await.unwrap();
+        sender.send_data("lo".into()).await.unwrap();
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        sender.send_trailers(trailers).await.unwrap();
+    });
+
+    // HTTP/1 trailers are discarded by hyper
+    let client = hyper::Client::builder().http2_only(true).build_http();
+    let request = hyper::Request::post(format!("http://{}/", addr))
+        .body(body)
+        .unwrap();
+
+    let response = tokio::time::timeout(Duration::from_secs(5), client.request(request))
+        .await
+        .unwrap()
+        .unwrap();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+    assert_eq!(body, r#"body has not been read to completion b"hello" "0""#);
+}
+
+#[derive(Context)]
+struct Upload {
+    #[cx(body_stream = BodyConfig::new().limit(8).timeout(Duration::from_millis(10)))]
+    body: BodyStream,
+}
+
+async fn upload(mut cx: Upload) -> Result<String, BodyRejection> {
+    let mut read = 0;
+    while let Some(chunk) = cx.body.chunk().await {
+        read += chunk?.len();
+    }
+    Ok(read.to_string())
+}
+
+#[tokio::test]
+async fn stream() {
+    let bison = Bison::new().post("/", upload);
+
+    let chunks = vec![Ok::<_, std::io::Error>("hel".into()), Ok("lo".into())];
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .body(Body::stream(Iter(chunks.into_iter())))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.body().buffer(16).await.unwrap(), "5");
+
+    let chunks = vec![Ok::<_, std::io::Error>("hello".into()), Ok("world".into())];
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .body(Body::stream(Iter(chunks.into_iter())))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .body(Body::stream(Pending))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+}
+
+#[cfg(feature = "decompression")]
+#[tokio::test]
+async fn unsupported_encoding() {
+    let bison = Bison::new().post("/", |cx: Echo| async move { cx.body });
+
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .header("content-encoding", "compress")
+                .body(Body::once("hello"))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[cfg(not(feature = "decompression"))]
+#[tokio::test]
+async fn encoded_passthrough() {
+    let bison = Bison::new().post("/", |cx: Echo| async move { cx.body });
+
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .header("content-encoding", "gzip")
+                .body(Body::once("hello"))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body().buffer(16).await.unwrap(), "hello");
+}
+
+#[cfg(feature = "decompression")]
+#[tokio::test]
+async fn decompression() {
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    let bison = Bison::new().post("/", |cx: Echo| async move { cx.body });
+
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&[b'a'; 1024]).unwrap();
+    let gzipped = encoder.finish().unwrap();
+
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .header("content-encoding", "gzip")
+                .body(Body::once(gzipped.clone()))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(
+        response.body().buffer(2048).await.unwrap(),
+        [b'a'; 1024][..]
+    );
+
+    // the limit applies to the decompressed body
+    #[derive(Context)]
+    struct Small {
+        #[cx(body = BodyConfig::new().limit(512))]
+        body: String,
+    }
+
+    let bison = Bison::new().post("/", |cx: Small| async move { cx.body });
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .header("content-encoding", "gzip")
+                .body(Body::once(gzipped))
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+struct Pending;
+
+impl futures_core::Stream for Pending {
+    type Item = Result<bison::http::Bytes, std::io::Error>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        std::task::Poll::Pending
+    }
+}
+
+struct Iter<I>(I);
+
+impl<I: Iterator + Unpin> futures_core::Stream for Iter<I> {
+    type Item = I::Item;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        std::task::Poll::Ready(self.0.next())
+    }
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "cookies")]
+
+use bison::extract::{cookie, cookies, private_cookies, signed_cookies, Optional};
+use bison::http::cookie::*;
+use bison::http::{header, Body, StatusCode};
+use bison::{Bison, Context};
+
+#[tokio::test]
+async fn cookie_jar() {
+    #[derive(Context)]
+    struct Session {
+        #[cx(cookies)]
+        jar: CookieJar,
+        #[cx(cookie = "visits")]
+        visits: Optional<u32>,
+    }
+
+    async fn handler(cx: Session) -> (CookieJar, String) {
+        let visits = cx.visits.unwrap_or(0) + 1;
+        let body = format!("{:?} {}", cx.jar.get("theme").map(Cookie::value), visits);
+
+        let visit = Cookie::build(("visits", visits.to_string()))
+            .http_only(true)
+            .secure(true)
+            .same_site(SameSite::Strict)
+            .max_age(time::Duration::seconds(60));
+
+        (cx.jar.set(visit).remove("theme"), body)
+    }
+
+    let bison = Bison::new().get("/", handler);
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/")
+                .header(header::COOKIE, "theme=dark; visits=2")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    let set_cookie = response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .map(|value| value.to_str().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(set_cookie.len(), 2);
+    assert!(set_cookie
+        .iter()
+        .any(|c| c.starts_with("visits=3;") && c.contains("HttpOnly") && c.contains("Max-Age=60")));
+    assert!(set_cookie
+        .iter()
+        .any(|c| c.starts_with("theme=;") && c.contains("Max-Age=0")));
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"Some("dark") 3"#
+    );
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/")
+                .header(header::COOKIE, "visits=many")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "None 1");
+}
+
+#[tokio::test]
+async fn signed_and_private() {
+    #[derive(Context)]
+    struct Secure {
+        #[cx(signed_cookies)]
+        signed: SignedCookieJar,
+        #[cx(private_cookies)]
+        private: PrivateCookieJar,
+    }
+
+    async fn login(cx: Secure) -> (SignedCookieJar, (PrivateCookieJar, ())) {
+        (
+            cx.signed.set(("user", "alice")),
+            (cx.private.set(("token", "secret")), ()),
+        )
+    }
+
+    async fn read(cx: Secure) -> String {
+        format!(
+            "{:?} {:?}",
+            cx.signed.get("user").map(|c| c.value().to_owned()),
+            cx.private.get("token").map(|c| c.value().to_owned())
+        )
+    }
+
+    let bison = Bison::new()
+        .inject(Key::generate())
+        .get("/login", login)
+        .get("/read", read);
+
+    let response = bison
+        .serve_one(http::Request::get("/login").body(Body::empty()).unwrap())
+        .await;
+
+    let cookies = response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .map(|value| value.to_str().unwrap().split(';').next().unwrap())
+        .collect::<Vec<_>>();
+
+    assert!(cookies.iter().all(|c| !c.contains("secret")));
+
+    let send = |cookie: String| {
+        bison.serve_one(
+            http::Request::get("/read")
+                .header(header::COOKIE, cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = send(cookies.join("; ")).await;
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"Some("alice") Some("secret")"#
+    );
+
+    let response = send("user=alice; token=secret".to_owned()).await;
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "None None");
+
+    let response = Bison::new()
+        .get("/read", read)
+        .serve_one(http::Request::get("/read").body(Body::empty()).unwrap())
+        .await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
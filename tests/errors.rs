@@ -0,0 +1,441 @@
+use bison::extract::{path, query};
+use bison::http::{header, Body, StatusCode};
+use bison::reject::ErrorFormat;
+use bison::{Bison, Context};
+
+#[derive(Context)]
+struct GetUser {
+    #[cx(path)]
+    id: u32,
+    #[cx(query)]
+    page: u32,
+}
+
+async fn get_user(cx: GetUser) -> String {
+    format!("{} {}", cx.id, cx.page)
+}
+
+fn get(uri: &str) -> http::Request<Body> {
+    http::Request::get(uri).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn error_formats() {
+    use bison::{wrap_fn, Rejection, Request};
+
+    let response = Bison::new()
+        .get("/users/:id", get_user)
+        .serve_one(get("/users/x?page=1"))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "");
+
+    let response = Bison::new()
+        .get("/users/:id", get_user)
+        .error_format(ErrorFormat::Plain)
+        .serve_one(get("/users/x?page=1"))
+        .await;
+
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "error extracting route param 'id': invalid digit found in string"
+    );
+
+    let response = Bison::new()
+        .get("/users/:id", get_user)
+        .error_format(ErrorFormat::Json)
+        .serve_one(get("/users/1"))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"{"status":404,"message":"query parameter 'page' not found","field":"page"}"#
+    );
+
+    let response = Bison::new()
+        .get("/users/:id", get_user)
+        .error_format(ErrorFormat::Problem)
+        .serve_one(get("/users/1?page=two"))
+        .await;
+
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/problem+json"
+    );
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"{"type":"about:blank","title":"Bad Request","status":400,"#.to_owned()
+            + r#""detail":"failed to deserialize `u32` from query parameter: invalid digit found in string","field":"page"}"#
+    );
+
+    // errors from outside of extractors are rendered the same way
+    let response = Bison::new()
+        .get("/users/:id", get_user)
+        .error_format(ErrorFormat::Plain)
+        .wrap(wrap_fn!(async |req: Request, next| {
+            req.body().buffer(4).await.map_err(Rejection::from)?;
+            next.call(req).await
+        }))
+        .serve_one(
+            http::Request::get("/users/1?page=1")
+                .body(Body::once("hello"))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "body larger than limit of 4 bytes"
+    );
+}
+
+#[tokio::test]
+#[cfg(feature = "json")]
+async fn json_error_location() {
+    use bison::extract::json;
+
+    #[derive(serde::Deserialize)]
+    struct User {
+        name: String,
+    }
+
+    #[derive(Context)]
+    struct CreateUser {
+        #[cx(json)]
+        user: User,
+    }
+
+    async fn create_user(cx: CreateUser) -> String {
+        cx.user.name
+    }
+
+    let response = Bison::new()
+        .post("/", create_user)
+        .error_format(ErrorFormat::Json)
+        .serve_one(
+            http::Request::post("/")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::once("{\n  \"name\": 1\n}"))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"{"status":400,"message":"failed to deserialize body: invalid type: integer `1`, expected a string at line 2 column 11","line":2,"column":11}"#
+    );
+}
+
+#[tokio::test]
+async fn on_rejection() {
+    use bison::http::ResponseBuilder;
+    use bison::{Rejection, Request, Response};
+
+    fn teapot(rejection: Rejection, _: &Request) -> Response {
+        ResponseBuilder::new()
+            .status(StatusCode::IM_A_TEAPOT)
+            .body(Body::once(rejection.to_string()))
+            .unwrap()
+    }
+
+    let bison = Bison::new()
+        .get("/users/:id", get_user)
+        .scope("/admin", |scope| {
+            scope
+                .get("/users/:id", get_user)
+                .on_rejection(|rejection, req| rejection.reject(req))
+        })
+        .on_rejection(teapot);
+
+    let response = bison.serve_one(get("/users/1")).await;
+    assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "query parameter 'page' not found"
+    );
+
+    let response = bison.serve_one(get("/missing")).await;
+    assert_eq!(response.status(), StatusCode::IM_A_TEAPOT);
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "404 not found");
+
+    let response = bison.serve_one(get("/admin/users/x?page=1")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn downcast_rejection() {
+    use bison::extract::QueryRejection;
+    use bison::reject::NotFound;
+    use bison::{wrap_fn, Rejection, Request};
+
+    let bison =
+        Bison::new()
+            .get("/users/:id", get_user)
+            .wrap(wrap_fn!(async |req: Request, next| {
+                let rejection = match next.call(req).await {
+                    Ok(response) => return Ok(response),
+                    Err(rejection) => rejection,
+                };
+
+                assert!(!rejection.is::<NotFound>());
+                assert_eq!(rejection.status(), StatusCode::BAD_REQUEST);
+
+                match rejection.downcast::<QueryRejection>() {
+                    Ok(rejection) if rejection.field() == Some("page") => {
+                        Err(Rejection::from(NotFound))
+                    }
+                    Ok(rejection) => Err(rejection.into()),
+                    Err(rejection) => Err(rejection),
+                }
+            }));
+
+    let response = bison.serve_one(get("/users/1?page=x")).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = bison.serve_one(get("/users/x?page=1")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn derive_reject() {
+    use bison::reject::Reject;
+
+    #[derive(Debug, bison::Reject)]
+    enum UserError {
+        #[reject(status = 404, message = "user {id} does not exist")]
+        NotFound { id: u32 },
+        #[reject(status = 403, message = "{0} may not view user {1:?}")]
+        Forbidden(&'static str, u32),
+        #[reject(status = 503)]
+        Unavailable,
+    }
+
+    #[derive(Debug, bison::Reject)]
+    #[reject(status = 400, message = "bad request")]
+    struct BadRequest;
+
+    assert_eq!(
+        UserError::NotFound { id: 1 }.to_string(),
+        "user 1 does not exist"
+    );
+    assert_eq!(
+        UserError::Forbidden("ferris", 2).to_string(),
+        "ferris may not view user 2"
+    );
+    assert_eq!(UserError::Unavailable.to_string(), "Service Unavailable");
+    assert_eq!(
+        UserError::Unavailable.status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+    assert_eq!(BadRequest.status(), StatusCode::BAD_REQUEST);
+
+    async fn get_user(cx: GetUser) -> Result<String, UserError> {
+        match cx.id {
+            1 => Ok(format!("{} {}", cx.id, cx.page)),
+            2 => Err(UserError::Forbidden("ferris", 2)),
+            id => Err(UserError::NotFound { id }),
+        }
+    }
+
+    let bison = Bison::new()
+        .get("/users/:id", get_user)
+        .error_format(ErrorFormat::Plain);
+
+    let response = bison.serve_one(get("/users/3?page=1")).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "user 3 does not exist"
+    );
+
+    let response = bison.serve_one(get("/users/2?page=1")).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[cfg(feature = "json")]
+async fn debug_mode() {
+    let bison = Bison::new().get("/users/:id", get_user).debug(true);
+
+    let response = bison.serve_one(get("/users/x?page=1")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/html; charset=utf-8"
+    );
+
+    let body = response.body().buffer(1 << 20).await.unwrap();
+    let body = std::str::from_utf8(&body).unwrap();
+    assert!(body.contains("<h1>400 Bad Request</h1>"));
+    assert!(body.contains("<tr><th>Route</th><td>/users/:id</td></tr>"));
+    assert!(body.contains("<li>invalid digit found in string</li>"));
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/users/x?page=1")
+                .header(header::ACCEPT, "application/json")
+                .header("x-request-id", "abc")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+
+    let body = response.body().buffer(1 << 20).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["status"], 400);
+    assert_eq!(body["route"], "/users/:id");
+    assert_eq!(body["params"]["id"], "x");
+    assert_eq!(body["headers"]["x-request-id"], "abc");
+    assert_eq!(body["sources"][0], "invalid digit found in string");
+    assert!(body["debug"].as_str().unwrap().contains("PathRejection"));
+
+    let response = bison.serve_one(get("/missing")).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = response.body().buffer(1 << 20).await.unwrap();
+    assert!(std::str::from_utf8(&body)
+        .unwrap()
+        .contains("404 Not Found"));
+
+    let response = Bison::new()
+        .get("/users/:id", get_user)
+        .debug(false)
+        .serve_one(get("/users/x?page=1"))
+        .await;
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "");
+}
+
+#[tokio::test]
+async fn catch_panic() {
+    use bison::wrap::{CatchPanic, Panicked};
+    use bison::Request;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Context)]
+    struct Explode {
+        #[cx(path)]
+        id: u32,
+    }
+
+    async fn explode(cx: Explode) -> &'static str {
+        if cx.id == 0 {
+            panic!("boom");
+        }
+
+        "ok"
+    }
+
+    let panics = Arc::new(Mutex::new(Vec::new()));
+    let reported = panics.clone();
+
+    let bison = Bison::new()
+        .get("/explode/:id", explode)
+        .on_rejection(|rejection, req| {
+            assert!(rejection.is::<Panicked>());
+            rejection.reject(req)
+        })
+        .error_format(ErrorFormat::Plain)
+        .catch_panic(
+            CatchPanic::new().on_panic(move |panicked: &Panicked, req: &Request| {
+                let route = req.route().unwrap_or_default().to_owned();
+                reported
+                    .lock()
+                    .unwrap()
+                    .push((route, panicked.message().to_owned()));
+            }),
+        );
+
+    let response = bison.serve_one(get("/explode/0")).await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "internal server error"
+    );
+    assert_eq!(
+        *panics.lock().unwrap(),
+        [("/explode/:id".to_owned(), "boom".to_owned())]
+    );
+
+    let response = bison.serve_one(get("/explode/1")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = Bison::new()
+        .get("/explode/:id", explode)
+        .wrap(CatchPanic::new())
+        .serve_one(get("/explode/0"))
+        .await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn http_error() {
+    use bison::reject::{HttpError, Reject};
+    use std::time::Duration;
+
+    #[derive(Context)]
+    struct Login {
+        #[cx(path)]
+        attempt: u32,
+    }
+
+    async fn login(cx: Login) -> Result<&'static str, HttpError> {
+        match cx.attempt {
+            0 => Err(HttpError::unauthorized(r#"Bearer realm="api""#)),
+            1 => Err(HttpError::too_many_requests()
+                .message("slow down")
+                .retry_after(Duration::from_secs(30))),
+            2 => Err(HttpError::bad_request()
+                .header(header::CONTENT_TYPE, "text/csv".parse().unwrap())
+                .body("attempt\n2\n")),
+            _ => Ok("welcome"),
+        }
+    }
+
+    let bison = Bison::new()
+        .get("/login/:attempt", login)
+        .error_format(ErrorFormat::Plain);
+
+    let response = bison.serve_one(get("/login/0")).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        response.headers()[header::WWW_AUTHENTICATE],
+        r#"Bearer realm="api""#
+    );
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "Unauthorized");
+
+    let response = bison.serve_one(get("/login/1")).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "slow down");
+
+    let response = bison.serve_one(get("/login/2")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "attempt\n2\n");
+
+    let response = bison
+        .serve_one(http::Request::post("/login/3").body(Body::empty()).unwrap())
+        .await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[header::ALLOW], "GET, OPTIONS");
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "Method Not Allowed"
+    );
+
+    let error =
+        HttpError::service_unavailable().with_source(std::io::Error::other("database down"));
+    assert_eq!(error.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(Reject::source(&error).unwrap().to_string(), "database down");
+}
@@ -0,0 +1,148 @@
+#![cfg(any(feature = "msgpack", feature = "cbor", feature = "protobuf"))]
+
+use bison::http::{header, Body, StatusCode};
+use bison::{Bison, Context};
+
+fn post(content_type: &str, body: Vec<u8>) -> http::Request<Body> {
+    http::Request::post("/")
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::once(body))
+        .unwrap()
+}
+
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[tokio::test]
+#[cfg(feature = "msgpack")]
+async fn msgpack() {
+    use bison::extract::{msgpack, MsgPackConfig};
+    use bison::respond::MsgPack;
+
+    #[derive(Context)]
+    struct Move {
+        #[cx(msgpack = MsgPackConfig::new().limit(64))]
+        point: Point,
+    }
+
+    async fn handler(cx: Move) -> MsgPack<Point> {
+        MsgPack(Point {
+            x: cx.point.x + 1,
+            y: cx.point.y + 1,
+        })
+    }
+
+    let bison = Bison::new().post("/", handler);
+
+    let body = rmp_serde::to_vec(&Point { x: 1, y: 2 }).unwrap();
+    let response = bison.serve_one(post("application/msgpack", body)).await;
+
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/msgpack"
+    );
+
+    let body = response.body().buffer(1024).await.unwrap();
+    let point: Point = rmp_serde::from_slice(&body).unwrap();
+    assert_eq!(point, Point { x: 2, y: 3 });
+
+    let body = rmp_serde::to_vec(&Point { x: 1, y: 2 }).unwrap();
+    let response = bison.serve_one(post("application/json", body)).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = bison
+        .serve_one(post("application/x-msgpack", vec![0xc1]))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = bison
+        .serve_one(post("application/msgpack", vec![0; 128]))
+        .await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+#[cfg(feature = "cbor")]
+async fn cbor() {
+    use bison::extract::cbor;
+    use bison::respond::Cbor;
+
+    #[derive(Context)]
+    struct Move {
+        #[cx(cbor)]
+        point: Point,
+    }
+
+    async fn handler(cx: Move) -> Cbor<Point> {
+        Cbor(Point {
+            x: cx.point.y,
+            y: cx.point.x,
+        })
+    }
+
+    let bison = Bison::new().post("/", handler);
+
+    let mut body = Vec::new();
+    ciborium::ser::into_writer(&Point { x: 1, y: 2 }, &mut body).unwrap();
+    let response = bison.serve_one(post("application/cbor", body)).await;
+
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/cbor");
+
+    let body = response.body().buffer(1024).await.unwrap();
+    let point: Point = ciborium::de::from_reader(&body[..]).unwrap();
+    assert_eq!(point, Point { x: 2, y: 1 });
+
+    let response = bison.serve_one(post("application/cbor", vec![0xff])).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[cfg(feature = "protobuf")]
+async fn protobuf() {
+    use bison::extract::protobuf;
+    use bison::respond::Protobuf;
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Greeting {
+        #[prost(string, tag = "1")]
+        name: String,
+    }
+
+    #[derive(Context)]
+    struct Greet {
+        #[cx(protobuf)]
+        greeting: Greeting,
+    }
+
+    async fn handler(cx: Greet) -> Protobuf<Greeting> {
+        Protobuf(Greeting {
+            name: format!("hello {}", cx.greeting.name),
+        })
+    }
+
+    let bison = Bison::new().post("/", handler);
+
+    let body = Greeting {
+        name: "ferris".into(),
+    }
+    .encode_to_vec();
+    let response = bison.serve_one(post("application/x-protobuf", body)).await;
+
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/protobuf"
+    );
+
+    let body = response.body().buffer(1024).await.unwrap();
+    assert_eq!(Greeting::decode(body).unwrap().name, "hello ferris");
+
+    let response = bison
+        .serve_one(post("application/protobuf", vec![0x0a, 0x05]))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,140 @@
+use bison::extract::{typed_header, Optional};
+use bison::http::headers::*;
+use bison::http::{header, Body, StatusCode};
+use bison::{Bison, Context, Respond};
+
+use std::time::Duration;
+
+#[tokio::test]
+async fn typed_headers() {
+    #[derive(Context)]
+    struct Headers {
+        #[cx(typed_header)]
+        accept: Accept,
+        #[cx(typed_header)]
+        authorization: Authorization,
+        #[cx(typed_header)]
+        range: Optional<Range>,
+        #[cx(typed_header)]
+        cookie: Cookie,
+        #[cx(typed_header)]
+        if_none_match: Optional<IfNoneMatch>,
+    }
+
+    async fn handler(cx: Headers) -> impl Respond {
+        let preferred = cx.accept.iter().next().unwrap().value.to_string();
+        let json = cx.accept.quality(&"application/json".parse().unwrap());
+        let png = cx.accept.accepts(&"image/png".parse().unwrap());
+        let range = cx
+            .range
+            .as_ref()
+            .map(|range| range.ranges()[0].resolve(1000));
+        let cached = cx
+            .if_none_match
+            .as_ref()
+            .is_some_and(|tags| tags.matches(&ETag::strong("v1")));
+
+        let body = format!(
+            "{} {} {} {:?} {:?} {:?} {}",
+            preferred,
+            json,
+            png,
+            cx.authorization,
+            range,
+            cx.cookie.get("session"),
+            cached
+        );
+
+        let cache = CacheControl {
+            private: true,
+            max_age: Some(Duration::from_secs(60)),
+            ..CacheControl::default()
+        };
+
+        body.with_header(ETag::weak("v1")).with_header(cache)
+    }
+
+    let bison = Bison::new().get("/", handler);
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/")
+                .header(
+                    header::ACCEPT,
+                    "application/json;q=0.5, text/html, */*;q=0.1",
+                )
+                .header(header::AUTHORIZATION, "Basic dXNlcjpwYXNz")
+                .header(header::RANGE, "bytes=-100")
+                .header(header::COOKIE, "theme=dark; session=abc")
+                .header(header::IF_NONE_MATCH, r#"W/"v1", "v2""#)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ETAG], r#"W/"v1""#);
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        "private, max-age=60"
+    );
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"text/html 0.5 true Basic { username: "user", password: "pass" } Some(Some(900..1000)) Some("abc") true"#
+    );
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/")
+                .header(header::ACCEPT, "text/html")
+                .header(header::AUTHORIZATION, "Digest realm")
+                .header(header::COOKIE, "")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn encode_decode() {
+    let accept = Accept::decode(&["text/*;q=0.3, text/html;level=1;q=0.7", "text/plain"]).unwrap();
+    assert_eq!(
+        accept.encode().unwrap(),
+        "text/plain, text/html;level=1;q=0.7, text/*;q=0.3"
+    );
+    assert_eq!(accept.quality(&mime::TEXT_CSS), 0.3);
+    assert!(!accept.accepts(&mime::IMAGE_PNG));
+    assert!(Accept::decode(&["text/html;q=2"]).is_err());
+
+    let auth = Authorization::decode(&["bearer abc.def"]).unwrap();
+    assert_eq!(auth, Authorization::bearer("abc.def"));
+    assert_eq!(
+        Authorization::basic("user", "pass").encode().unwrap(),
+        "Basic dXNlcjpwYXNz"
+    );
+
+    let cache = CacheControl::decode(&["no-cache, max-age=\"30\"", "immutable, x-custom"]).unwrap();
+    assert!(cache.no_cache && cache.immutable);
+    assert_eq!(cache.max_age, Some(Duration::from_secs(30)));
+    assert!(CacheControl::decode(&["max-age=soon"]).is_err());
+
+    let range = Range::decode(&["bytes=0-99, 500-"]).unwrap();
+    assert_eq!(
+        range.ranges(),
+        [ByteRange::Bounded(0, 99), ByteRange::From(500)]
+    );
+    assert_eq!(range.encode().unwrap(), "bytes=0-99, 500-");
+    assert_eq!(ByteRange::From(500).resolve(100), None);
+    assert!(Range::decode(&["items=0-1"]).is_err());
+    assert!(Range::decode(&["bytes=5-1"]).is_err());
+
+    assert_eq!(IfNoneMatch::decode(&["*"]).unwrap(), IfNoneMatch::Any);
+    assert!(IfNoneMatch::decode(&["v1"]).is_err());
+    assert!(UserAgent::new("bad\nagent").encode().is_err());
+
+    let cookie = Cookie::new().add("a", "1").add("b", "2");
+    assert_eq!(cookie.encode().unwrap(), "a=1; b=2");
+    assert_eq!(ContentType::json().encode().unwrap(), "application/json");
+}
@@ -0,0 +1,158 @@
+#![cfg(feature = "multipart")]
+
+use bison::extract::{multipart, multipart_form, Multipart, MultipartConfig, MultipartForm};
+use bison::http::{header, Body, StatusCode};
+use bison::{Bison, Context, Rejection};
+
+use serde::Deserialize;
+
+const BOUNDARY: &str = "X-BOUNDARY";
+
+fn form(parts: &[(&str, Option<&str>, &str)]) -> http::Request<Body> {
+    let mut body = String::new();
+
+    for (name, file_name, value) in parts {
+        body.push_str(&format!("--{}\r\n", BOUNDARY));
+
+        match file_name {
+            Some(file_name) => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+                 Content-Type: text/plain\r\n\r\n",
+                name, file_name
+            )),
+            None => body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                name
+            )),
+        }
+
+        body.push_str(value);
+        body.push_str("\r\n");
+    }
+
+    body.push_str(&format!("--{}--\r\n", BOUNDARY));
+
+    http::Request::post("/")
+        .header(
+            header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        )
+        .body(Body::once(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn fields() {
+    #[derive(Context)]
+    struct Upload {
+        #[cx(multipart = MultipartConfig::new().field_limit(16))]
+        form: Multipart,
+    }
+
+    async fn handler(mut cx: Upload) -> Result<String, Rejection> {
+        let mut out = Vec::new();
+
+        while let Some(mut field) = cx.form.next_field().await? {
+            let mut len = 0;
+            while let Some(chunk) = field.chunk().await? {
+                len += chunk.len();
+            }
+
+            out.push(format!(
+                "{}:{:?}:{:?}:{}",
+                field.name().unwrap(),
+                field.file_name(),
+                field.content_type().map(ToString::to_string),
+                len
+            ));
+        }
+
+        Ok(out.join(" "))
+    }
+
+    let bison = Bison::new().post("/", handler);
+
+    let response = bison
+        .serve_one(form(&[
+            ("title", None, "hello"),
+            ("doc", Some("a.txt"), "file contents"),
+        ]))
+        .await;
+
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"title:None:None:5 doc:Some("a.txt"):Some("text/plain"):13"#
+    );
+
+    let response = bison
+        .serve_one(form(&[("title", None, "a value that is too long")]))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .header(header::CONTENT_TYPE, "text/plain")
+                .body(Body::once("hello"))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn form_with_files() {
+    #[derive(Deserialize)]
+    struct Post {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    #[derive(Context)]
+    struct Upload {
+        #[cx(multipart_form = MultipartConfig::new().limit(512))]
+        form: MultipartForm<Post>,
+    }
+
+    async fn handler(cx: Upload) -> String {
+        let file = cx.form.file("attachment").unwrap();
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+
+        format!(
+            "{} {:?} {:?} {} {}",
+            cx.form.fields.title,
+            cx.form.fields.tags,
+            file.file_name(),
+            file.size(),
+            contents
+        )
+    }
+
+    let bison = Bison::new().post("/", handler);
+
+    let response = bison
+        .serve_one(form(&[
+            ("title", None, "notes"),
+            ("tags", None, "a"),
+            ("attachment", Some("notes.txt"), "some notes"),
+            ("tags", None, "b"),
+        ]))
+        .await;
+
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"notes ["a", "b"] Some("notes.txt") 10 some notes"#
+    );
+
+    let response = bison.serve_one(form(&[("tags", None, "a")])).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = bison
+        .serve_one(form(&[("title", None, &"x".repeat(1024))]))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "multipart")]
+
+// changing the temporary directory affects the whole process,
+// so this runs separately from the other multipart tests
+
+use bison::extract::{multipart_form, MultipartConfig};
+use bison::http::{header, Body, StatusCode};
+use bison::{Bison, Reject, Request};
+
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Post {}
+
+#[tokio::test]
+async fn upload_io_error() {
+    async fn handler(req: Request) -> String {
+        let err = multipart_form::<Post>(&req, MultipartConfig::new())
+            .await
+            .err()
+            .unwrap();
+
+        let status = err.status();
+        let response = err.reject(&req);
+        format!("{} {}", status.as_u16(), response.status().as_u16())
+    }
+
+    std::env::set_var("TMPDIR", "/nonexistent/bison");
+
+    let bison = Bison::new().post("/", handler);
+
+    let body = "--X-BOUNDARY\r\n\
+                Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\r\n\
+                contents\r\n\
+                --X-BOUNDARY--\r\n";
+
+    let response = bison
+        .serve_one(
+            http::Request::post("/")
+                .header(
+                    header::CONTENT_TYPE,
+                    "multipart/form-data; boundary=X-BOUNDARY",
+                )
+                .body(Body::once(body))
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "500 500");
+}
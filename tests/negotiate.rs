@@ -0,0 +1,149 @@
+use bison::extract::{negotiation, Negotiation};
+use bison::http::headers::{AcceptEncoding, AcceptLanguage, TypedHeader};
+use bison::http::{header, Body, StatusCode};
+use bison::respond::Negotiate;
+use bison::{Bison, Context};
+
+#[test]
+fn accept_headers() {
+    let language = AcceptLanguage::decode(&["fr-CH, fr;q=0.9, en;q=0.8, *;q=0.1"]).unwrap();
+    assert_eq!(language.quality("fr-CH"), 1.0);
+    assert_eq!(language.quality("fr-FR"), 0.9);
+    assert_eq!(language.quality("EN-us"), 0.8);
+    assert_eq!(language.quality("de"), 0.1);
+    assert_eq!(language.preferred(&["de", "en-GB", "fr"]), Some("fr"));
+    assert_eq!(
+        language.encode().unwrap(),
+        "fr-CH, fr;q=0.9, en;q=0.8, *;q=0.1"
+    );
+
+    let encoding = AcceptEncoding::decode(&["gzip;q=0.5", "br"]).unwrap();
+    assert_eq!(encoding.preferred(&["gzip", "br"]), Some("br"));
+    assert!(encoding.accepts("identity"));
+    assert!(!encoding.accepts("deflate"));
+
+    let encoding = AcceptEncoding::decode(&["gzip, *;q=0"]).unwrap();
+    assert_eq!(encoding.preferred(&["identity", "br"]), None);
+
+    assert!(AcceptLanguage::decode(&["en;q=2"]).is_err());
+}
+
+#[tokio::test]
+async fn negotiate_representation() {
+    #[derive(Context)]
+    struct Report {
+        #[cx(negotiation)]
+        negotiation: Negotiation,
+    }
+
+    async fn handler(cx: Report) -> Negotiate<Vec<u32>> {
+        Negotiate::new(&cx.negotiation, vec![1, 2, 3])
+            .html(|values| format!("<p>{:?}</p>", values))
+            .csv(|values| {
+                let values = values.iter().map(u32::to_string).collect::<Vec<_>>();
+                format!("{}\n", values.join(","))
+            })
+    }
+
+    async fn language(cx: Report) -> String {
+        format!(
+            "{:?} {:?}",
+            cx.negotiation.language(&["en", "de-DE"]),
+            cx.negotiation.charset(&["utf-8"])
+        )
+    }
+
+    let bison = Bison::new().get("/", handler).get("/language", language);
+
+    let get = |accept: Option<&'static str>| {
+        let mut req = http::Request::get("/");
+
+        if let Some(accept) = accept {
+            req = req.header(header::ACCEPT, accept);
+        }
+
+        bison.serve_one(req.body(Body::empty()).unwrap())
+    };
+
+    let response = get(Some("text/csv, text/html;q=0.9")).await;
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/csv; charset=utf-8"
+    );
+    assert_eq!(response.headers()[header::VARY], "accept");
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "1,2,3\n");
+
+    let response = get(Some("text/*")).await;
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "<p>[1, 2, 3]</p>"
+    );
+
+    let response = get(None).await;
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/html; charset=utf-8"
+    );
+
+    let response = get(Some("application/json")).await;
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(response.headers()[header::VARY], "accept");
+
+    let response = get(Some("text/html;q=x")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/language")
+                .header(header::ACCEPT_LANGUAGE, "de, en;q=0.5")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"Some("de-DE") Some("utf-8")"#
+    );
+}
+
+#[tokio::test]
+#[cfg(feature = "json")]
+async fn negotiate_json() {
+    #[derive(serde::Serialize)]
+    struct Visits {
+        visits: u32,
+    }
+
+    #[derive(Context)]
+    struct Report {
+        #[cx(negotiation)]
+        negotiation: Negotiation,
+    }
+
+    async fn handler(cx: Report) -> Negotiate<Visits> {
+        Negotiate::new(&cx.negotiation, Visits { visits: 7 })
+            .html(|report| format!("<p>{}</p>", report.visits))
+            .json()
+    }
+
+    let response = Bison::new()
+        .get("/", handler)
+        .serve_one(
+            http::Request::get("/")
+                .header(header::ACCEPT, "text/html;q=0.5, application/*")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"{"visits":7}"#
+    );
+}
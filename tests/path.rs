@@ -0,0 +1,111 @@
+use bison::bounded::BoxFuture;
+use bison::extract::{self, path_all};
+use bison::http::{Body, StatusCode};
+use bison::{Bison, Context, Rejection, Request};
+
+use serde::Deserialize;
+
+#[tokio::test]
+async fn path_all_struct_and_tuple() {
+    #[derive(Deserialize)]
+    struct Issue {
+        org: String,
+        repo: String,
+        n: u32,
+    }
+
+    #[derive(Context)]
+    struct GetIssue {
+        #[cx(path_all)]
+        issue: Issue,
+        #[cx(path_all)]
+        tuple: (String, String, u32),
+    }
+
+    async fn handler(cx: GetIssue) -> String {
+        format!(
+            "{}/{}#{} {:?}",
+            cx.issue.org, cx.issue.repo, cx.issue.n, cx.tuple
+        )
+    }
+
+    let bison = Bison::new().get("/orgs/:org/repos/:repo/issues/:n", handler);
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/orgs/rust/repos/bison/issues/36")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"rust/bison#36 ("rust", "bison", 36)"#
+    );
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/orgs/rust/repos/bison/issues/latest")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn path_all_manual_context() {
+    struct GetIssue {
+        message: String,
+    }
+
+    impl Context for GetIssue {
+        type Future = BoxFuture<'static, Result<Self, Rejection>>;
+
+        fn extract(req: Request) -> Self::Future {
+            Box::pin(async move {
+                let params = req
+                    .params()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect::<Vec<_>>()
+                    .join("&");
+
+                let message = match extract::path_all::<(String, u8)>(&req, ()).await {
+                    Ok((org, n)) => format!("{} {} {}", params, org, n),
+                    Err(err) => format!("{:?} {}", err.field(), err),
+                };
+
+                Ok(GetIssue { message })
+            })
+        }
+    }
+
+    async fn handler(cx: GetIssue) -> String {
+        cx.message
+    }
+
+    let bison = Bison::new().get("/:org/:n", handler);
+
+    let response = bison
+        .serve_one(http::Request::get("/rust/7").body(Body::empty()).unwrap())
+        .await;
+
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "org=rust&n=7 rust 7"
+    );
+
+    let response = bison
+        .serve_one(http::Request::get("/rust/300").body(Body::empty()).unwrap())
+        .await;
+
+    // the message names the target type, which is not stable
+    let body = response.body().buffer(1024).await.unwrap();
+    let body = std::str::from_utf8(&body).unwrap();
+    assert!(body.starts_with("Some(\"n\") failed to deserialize"));
+    assert!(body.ends_with(
+        "from route params: invalid `n`: cannot parse '300': number too large to fit in target type"
+    ));
+}
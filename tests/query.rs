@@ -0,0 +1,115 @@
+use bison::extract::query_all;
+use bison::http::{Body, StatusCode};
+use bison::{wrap_fn, Bison, Context, Request};
+
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Order {
+    Asc,
+    Desc,
+}
+
+#[derive(Deserialize)]
+struct Filters {
+    name: Option<String>,
+    #[serde(default)]
+    page: usize,
+    tag: Vec<String>,
+    order: Order,
+}
+
+#[derive(Context)]
+struct Search {
+    #[cx(query_all)]
+    filters: Filters,
+}
+
+async fn search(cx: Search) -> String {
+    let order = match cx.filters.order {
+        Order::Asc => "asc",
+        Order::Desc => "desc",
+    };
+
+    format!(
+        "{:?} {} {:?} {}",
+        cx.filters.name, cx.filters.page, cx.filters.tag, order
+    )
+}
+
+async fn get(bison: &Bison<impl bison::Wrap>, uri: &str) -> (StatusCode, String) {
+    let response = bison
+        .serve_one(http::Request::get(uri).body(Body::empty()).unwrap())
+        .await;
+    let body = response.body().buffer(1024).await.unwrap();
+    (response.status(), String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn query_all_struct() {
+    let bison = Bison::new().get("/search", search);
+
+    let (status, body) = get(&bison, "/search?tag=a&order=desc&tag=b&page=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"None 2 ["a", "b"] desc"#);
+
+    let (status, body) = get(&bison, "/search?name=bison&tag=a&order=asc").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"Some("bison") 0 ["a"] asc"#);
+
+    let (status, _) = get(&bison, "/search?tag=a&order=asc&page=two").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn query_all_rejection() {
+    async fn handler(req: Request) -> String {
+        let err = query_all::<Filters>(&req, ()).await.err().unwrap();
+        format!("{:?}: {}", err.field(), err)
+    }
+
+    let bison = Bison::new().get("/search", handler);
+
+    // the message names the target type, which is not stable
+    let (_, body) = get(&bison, "/search?tag=a&order=asc&page=two").await;
+    assert!(body.starts_with("Some(\"page\"): failed to deserialize"));
+    assert!(body.ends_with(
+        "from query string: invalid `page`: cannot parse 'two': invalid digit found in string"
+    ));
+
+    let (_, body) = get(&bison, "/search?tag=a").await;
+    assert!(body.starts_with("Some(\"order\"): failed to deserialize"));
+    assert!(body.ends_with("from query string: invalid `order`: missing value"));
+}
+
+#[tokio::test]
+async fn query_after_set_uri() {
+    let bison = Bison::new()
+        .get("/search", search)
+        .wrap(wrap_fn!(async |req: Request, next| {
+            assert_eq!(req.query("order"), None);
+            req.set_uri("/search?order=asc&tag=a".parse().unwrap());
+            next.call(req).await
+        }));
+
+    let (status, body) = get(&bison, "/search").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"None 0 ["a"] asc"#);
+}
+
+#[tokio::test]
+async fn query_changed_by_set_uri() {
+    let bison = Bison::new()
+        .get("/search", search)
+        .wrap(wrap_fn!(async |req: Request, next| {
+            assert_eq!(req.query("order"), Some("desc"));
+            req.set_uri("/search?order=asc&tag=a".parse().unwrap());
+            assert_eq!(req.query("order"), Some("asc"));
+            next.call(req).await
+        }));
+
+    let (status, body) = get(&bison, "/search?order=desc&tag=b").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"None 0 ["a"] asc"#);
+}
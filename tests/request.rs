@@ -0,0 +1,96 @@
+use bison::http::{header, Body};
+use bison::{Bison, Request};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_access() {
+    async fn handler(req: Request) -> &'static str {
+        let tasks = (0..64)
+            .map(|i| {
+                let req = req.clone();
+                tokio::spawn(async move {
+                    for _ in 0..100 {
+                        if i % 2 == 0 {
+                            req.set_uri("/concurrent?i=0".parse().unwrap());
+                        }
+
+                        let _ = req.uri();
+                        let _ = req.headers().get(header::CONTENT_TYPE);
+                        let _ = req.body().try_clone();
+                        let _ = req.body().chunk().await;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        "done"
+    }
+
+    let bison = Bison::new().get("/concurrent", handler);
+
+    let response = bison
+        .serve_one(
+            http::Request::builder()
+                .uri("/concurrent")
+                .header(header::CONTENT_TYPE, "text/plain")
+                .body(Body::once("body"))
+                .unwrap(),
+        )
+        .await;
+
+    assert!(response.status().is_success());
+}
+
+#[tokio::test]
+async fn headers() {
+    use bison::extract::{header, Optional};
+    use bison::Context;
+
+    #[derive(Context)]
+    struct Headers {
+        #[cx(header = "x-api-key")]
+        key: String,
+        #[cx(header)]
+        user_agent: Optional<String>,
+        #[cx(header)]
+        accept_language: Vec<String>,
+        #[cx(header = "x-retries")]
+        retries: Optional<u8>,
+    }
+
+    async fn handler(cx: Headers) -> String {
+        format!(
+            "{} {:?} {:?} {:?}",
+            cx.key, cx.user_agent, cx.accept_language, cx.retries
+        )
+    }
+
+    let bison = Bison::new().get("/", handler);
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/")
+                .header("x-api-key", "secret")
+                .header("user-agent", "curl")
+                .header("accept-language", "en, fr")
+                .header("accept-language", "de")
+                .header("x-retries", "many")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"secret Some("curl") ["en", "fr", "de"] None"#
+    );
+
+    let response = bison
+        .serve_one(http::Request::get("/").body(Body::empty()).unwrap())
+        .await;
+
+    assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+}
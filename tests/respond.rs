@@ -0,0 +1,220 @@
+use bison::http::{header, Body, HeaderMap, HeaderValue, StatusCode};
+use bison::respond::{BoxResponder, Html, Text};
+use bison::{Bison, Context, Respond};
+
+#[tokio::test]
+#[cfg(feature = "json")]
+async fn json_responder() {
+    use bison::respond::Json;
+    use serde::Serialize;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct User {
+        id: u64,
+        name: &'static str,
+    }
+
+    async fn user() -> Json<User> {
+        Json(User {
+            id: 1,
+            name: "ferris",
+        })
+    }
+
+    async fn value() -> (StatusCode, serde_json::Value) {
+        (StatusCode::CREATED, json!({ "ok": true }))
+    }
+
+    async fn invalid() -> Json<std::collections::HashMap<Vec<u8>, u8>> {
+        Json([(vec![1], 1)].into_iter().collect())
+    }
+
+    let bison = Bison::new()
+        .get("/user", user)
+        .get("/value", value)
+        .get("/invalid", invalid);
+
+    let get =
+        |path: &'static str| bison.serve_one(http::Request::get(path).body(Body::empty()).unwrap());
+
+    let response = get("/user").await;
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"{"id":1,"name":"ferris"}"#
+    );
+
+    let response = get("/value").await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"{"ok":true}"#
+    );
+
+    let response = get("/invalid").await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+#[cfg(feature = "json")]
+async fn derive_respond() {
+    use serde::Serialize;
+
+    #[derive(Serialize, Respond)]
+    #[respond(json, status = 201)]
+    struct Created {
+        id: u64,
+    }
+
+    #[derive(Respond)]
+    #[respond(json)]
+    enum GetUser {
+        #[respond(status = 200)]
+        Found(Created),
+        #[respond(status = 404)]
+        NotFound,
+        #[respond(status = 410)]
+        Gone { reason: &'static str },
+    }
+
+    #[derive(Respond)]
+    struct Text(&'static str);
+
+    async fn create() -> Created {
+        Created { id: 1 }
+    }
+
+    #[derive(Context)]
+    struct UserId {
+        id: u64,
+    }
+
+    async fn get_user(cx: UserId) -> GetUser {
+        match cx.id {
+            1 => GetUser::Found(Created { id: 1 }),
+            2 => GetUser::Gone { reason: "deleted" },
+            _ => GetUser::NotFound,
+        }
+    }
+
+    async fn text() -> Text {
+        Text("hello")
+    }
+
+    let bison = Bison::new()
+        .post("/users", create)
+        .get("/users/:id", get_user)
+        .get("/text", text);
+
+    let response = bison
+        .serve_one(http::Request::post("/users").body(Body::empty()).unwrap())
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(response.body().buffer(1024).await.unwrap(), r#"{"id":1}"#);
+
+    let get =
+        |path: &'static str| bison.serve_one(http::Request::get(path).body(Body::empty()).unwrap());
+
+    let response = get("/users/1").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body().buffer(1024).await.unwrap(), r#"{"id":1}"#);
+
+    let response = get("/users/2").await;
+    assert_eq!(response.status(), StatusCode::GONE);
+    assert_eq!(response.body().buffer(1024).await.unwrap(), r#""deleted""#);
+
+    let response = get("/users/3").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+
+    let response = get("/text").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "hello");
+}
+
+#[tokio::test]
+async fn header_responders() {
+    #[derive(Context)]
+    struct Page {
+        id: u32,
+    }
+
+    async fn html() -> (
+        StatusCode,
+        [(header::HeaderName, HeaderValue); 2],
+        Html<String>,
+    ) {
+        (
+            StatusCode::CREATED,
+            [
+                (header::CACHE_CONTROL, HeaderValue::from_static("no-cache")),
+                (header::SET_COOKIE, HeaderValue::from_static("a=1")),
+            ],
+            Html(format!("<p>{}</p>", 1)),
+        )
+    }
+
+    async fn text() -> (HeaderMap, Text<&'static str>) {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+        (headers, Text("a,b\n"))
+    }
+
+    async fn page(cx: Page) -> BoxResponder {
+        match cx.id {
+            0 => Html("<p>home</p>").boxed(),
+            1 => (StatusCode::ACCEPTED, HeaderMap::new(), Text("one")).boxed(),
+            _ => Option::<Text<&'static str>>::None.boxed(),
+        }
+    }
+
+    let bison = Bison::new()
+        .get("/html", html)
+        .get("/text", text)
+        .get("/page/:id", page);
+
+    let get =
+        |path: &'static str| bison.serve_one(http::Request::get(path).body(Body::empty()).unwrap());
+
+    let response = get("/html").await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(response.headers()[header::CACHE_CONTROL], "no-cache");
+    assert_eq!(response.headers()[header::SET_COOKIE], "a=1");
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "<p>1</p>");
+
+    let response = get("/text").await;
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+    assert_eq!(
+        response
+            .headers()
+            .get_all(header::CONTENT_TYPE)
+            .iter()
+            .count(),
+        1
+    );
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "a,b\n");
+
+    let response = get("/page/0").await;
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "<p>home</p>");
+
+    let response = get("/page/1").await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+
+    let response = get("/page/2").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+
+use bison::extract::{typed_header, Optional};
+use bison::http::headers::LastEventId;
+use bison::http::{header, Body, Bytes, StatusCode};
+use bison::respond::{Event, KeepAlive, Sse};
+use bison::{Bison, Context, Respond};
+
+#[derive(Context)]
+struct Updates {
+    #[cx(typed_header)]
+    last_event_id: Optional<LastEventId>,
+}
+
+#[tokio::test]
+async fn events() {
+    async fn updates(cx: Updates) -> Sse<Events> {
+        let start = match &*cx.last_event_id {
+            Some(id) => id.as_str().parse::<u32>().unwrap() + 1,
+            None => 0,
+        };
+
+        let events = (start..start + 2)
+            .map(|i| {
+                Event::new()
+                    .id(i.to_string())
+                    .data(format!("update #{}", i))
+            })
+            .collect();
+
+        Sse::new(Events::finite(events))
+    }
+
+    let bison = Bison::new().get("/updates", updates);
+
+    let response = bison
+        .serve_one(http::Request::get("/updates").body(Body::empty()).unwrap())
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/event-stream"
+    );
+    assert_eq!(response.headers()[header::CACHE_CONTROL], "no-cache");
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "data:update #0\nid:0\n\ndata:update #1\nid:1\n\n"
+    );
+
+    let response = bison
+        .serve_one(
+            http::Request::get("/updates")
+                .header("last-event-id", "7")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        "data:update #8\nid:8\n\ndata:update #9\nid:9\n\n"
+    );
+}
+
+#[tokio::test]
+async fn event_fields() {
+    let event = Event::new()
+        .comment("hello")
+        .event("update")
+        .data("one\ntwo\r\nthree")
+        .id("1")
+        .retry(Duration::from_secs(5));
+
+    let response = Sse::new(Events::finite(vec![event, Event::new().data("")]))
+        .respond()
+        .unwrap();
+
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        ":hello\nevent:update\ndata:one\ndata:two\ndata:three\nid:1\nretry:5000\n\ndata:\n\n"
+    );
+}
+
+#[tokio::test]
+async fn keep_alive() {
+    let events = Events::pending(vec![Event::new().data("first")]);
+    let response = Sse::new(events)
+        .keep_alive(
+            KeepAlive::new()
+                .interval(Duration::from_millis(10))
+                .text("ping"),
+        )
+        .respond()
+        .unwrap();
+
+    let body = response.body();
+    assert_eq!(chunk(body).await, "data:first\n\n");
+    assert_eq!(chunk(body).await, ":ping\n\n");
+    assert_eq!(chunk(body).await, ":ping\n\n");
+}
+
+#[test]
+#[should_panic]
+fn event_id_newline() {
+    let _ = Event::new().id("1\n2");
+}
+
+async fn chunk(body: &Body) -> Bytes {
+    let next =
+        std::future::poll_fn(|cx| futures_core::Stream::poll_next(Pin::new(&mut &*body), cx));
+    tokio::time::timeout(Duration::from_secs(1), next)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap()
+}
+
+/// Yields the events, and then either ends or stays pending.
+struct Events {
+    events: VecDeque<Event>,
+    end: bool,
+}
+
+impl Events {
+    fn finite(events: Vec<Event>) -> Self {
+        Events {
+            events: events.into(),
+            end: true,
+        }
+    }
+
+    fn pending(events: Vec<Event>) -> Self {
+        Events {
+            events: events.into(),
+            end: false,
+        }
+    }
+}
+
+impl futures_core::Stream for Events {
+    type Item = Result<Event, Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        match self.events.pop_front() {
+            Some(event) => Poll::Ready(Some(Ok(event))),
+            None if self.end => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
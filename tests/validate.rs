@@ -0,0 +1,139 @@
+use bison::http::{header, Body, StatusCode};
+use bison::validate::{rules, ValidationErrors};
+use bison::{Bison, Context, Validate};
+
+use serde::Deserialize;
+
+#[derive(Deserialize, Validate)]
+struct Address {
+    #[validate(length(min = 1))]
+    city: String,
+}
+
+#[derive(Deserialize, Validate)]
+struct SignUp {
+    #[validate(length(min = 3, max = 10))]
+    username: String,
+    #[validate(email(message = "please enter a valid email"))]
+    email: String,
+    #[validate(range(min = 13, max = 120))]
+    age: Option<u8>,
+    #[validate(length(max = 2), nested)]
+    addresses: Vec<Address>,
+}
+
+fn post_json(body: &str) -> http::Request<Body> {
+    http::Request::post("/")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::once(body.to_owned()))
+        .unwrap()
+}
+
+#[tokio::test]
+#[cfg(feature = "json")]
+async fn validate_json() {
+    use bison::extract::json;
+
+    #[derive(Context)]
+    struct Register {
+        #[cx(json, validate)]
+        form: SignUp,
+    }
+
+    async fn handler(cx: Register) -> String {
+        cx.form.username
+    }
+
+    let bison = Bison::new().post("/", handler);
+
+    let response = bison
+        .serve_one(post_json(
+            r#"{"username":"ferris","email":"ferris@rust-lang.org","age":null,"addresses":[]}"#,
+        ))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body().buffer(1024).await.unwrap(), "ferris");
+
+    let response = bison
+        .serve_one(post_json(
+            r#"{"username":"fe","email":"ferris","age":7,"addresses":[{"city":"Berlin"},{"city":""}]}"#,
+        ))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(
+        response.body().buffer(1024).await.unwrap(),
+        r#"{"errors":[{"field":"username","code":"length","message":"length must be between 3 and 10"},"#
+            .to_owned()
+            + r#"{"field":"email","code":"email","message":"please enter a valid email"},"#
+            + r#"{"field":"age","code":"range","message":"must be between 13 and 120"},"#
+            + r#"{"field":"addresses[1].city","code":"length","message":"length must be at least 1"}]}"#
+    );
+
+    let response = bison.serve_one(post_json(r#"{"username":"ferris"}"#)).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn manual_validate() {
+    struct Range(u32, u32);
+
+    impl Validate for Range {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+
+            if let Err(message) = rules::range(&self.0, None, Some(self.1)) {
+                errors.add("", "range", message);
+            }
+
+            errors.into_result()
+        }
+    }
+
+    #[derive(Validate)]
+    struct Pair(#[validate(nested)] Range, #[validate(nested)] Option<Range>);
+
+    assert!(Pair(Range(1, 2), None).validate().is_ok());
+
+    let errors = Pair(Range(3, 2), Some(Range(5, 4))).validate().unwrap_err();
+    assert_eq!(
+        errors.to_string(),
+        "`0` must be at most 2, `1` must be at most 4"
+    );
+    assert_eq!(errors.field("1").count(), 1);
+    assert_eq!(errors.iter().next().unwrap().code(), "range");
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn validate_regex() {
+    use once_cell::sync::Lazy;
+    use regex::Regex;
+
+    static SLUG: Lazy<Regex> = Lazy::new(|| Regex::new("^[a-z-]+$").unwrap());
+
+    #[derive(Validate)]
+    struct Post {
+        #[validate(regex = "^[A-Z]")]
+        title: String,
+        #[validate(regex(pattern = SLUG, message = "invalid slug"))]
+        slug: &'static str,
+    }
+
+    let post = Post {
+        title: "Hello".to_owned(),
+        slug: "hello-world",
+    };
+    assert!(post.validate().is_ok());
+
+    let post = Post {
+        title: "hello".to_owned(),
+        slug: "Hello World",
+    };
+    assert_eq!(
+        post.validate().unwrap_err().to_string(),
+        "`title` must match the pattern `^[A-Z]`, `slug` invalid slug"
+    );
+}
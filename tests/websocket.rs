@@ -0,0 +1,122 @@
+#![cfg(feature = "websocket")]
+
+use bison::extract::websocket;
+use bison::http::websocket::{Message, WebSocketUpgrade};
+use bison::http::{header, Body, StatusCode};
+use bison::{Bison, Context, Respond};
+use bison_hyper::Serve;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message as ClientMessage;
+
+#[derive(Context)]
+struct Echo {
+    #[cx(websocket)]
+    ws: WebSocketUpgrade,
+}
+
+async fn echo(cx: Echo) -> impl Respond {
+    cx.ws
+        .protocols(["chat"])
+        .on_upgrade(|mut socket| async move {
+            let protocol = socket.protocol().unwrap_or("none").to_owned();
+            socket.send(Message::from(protocol)).await.unwrap();
+
+            while let Some(Ok(message)) = socket.recv().await {
+                match message {
+                    Message::Text(_) | Message::Binary(_) => socket.send(message).await.unwrap(),
+                    Message::Close(_) => break,
+                    _ => {}
+                }
+            }
+        })
+}
+
+#[tokio::test]
+async fn handshake() {
+    let bison = Bison::new().get("/echo", echo).post("/echo", echo);
+
+    let request = |method: &str, headers: &[(&str, &str)]| {
+        let mut request = http::Request::builder().method(method).uri("/echo");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request.body(Body::empty()).unwrap()
+    };
+
+    let valid = [
+        ("connection", "keep-alive, Upgrade"),
+        ("upgrade", "websocket"),
+        ("sec-websocket-version", "13"),
+        ("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ=="),
+    ];
+
+    let response = bison.serve_one(request("POST", &valid)).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[header::ALLOW], "GET");
+
+    let response = bison.serve_one(request("GET", &valid[2..])).await;
+    assert_eq!(response.status(), StatusCode::UPGRADE_REQUIRED);
+    assert_eq!(response.headers()[header::UPGRADE], "websocket");
+
+    let response = bison
+        .serve_one(request(
+            "GET",
+            &[valid[0], valid[1], ("sec-websocket-version", "8"), valid[3]],
+        ))
+        .await;
+    assert_eq!(response.status(), StatusCode::UPGRADE_REQUIRED);
+    assert_eq!(response.headers()[header::SEC_WEBSOCKET_VERSION], "13");
+
+    let response = bison.serve_one(request("GET", &valid[..3])).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // the request is valid, but there is no server to upgrade the connection
+    let response = bison.serve_one(request("GET", &valid)).await;
+    assert_eq!(response.status(), StatusCode::UPGRADE_REQUIRED);
+}
+
+#[tokio::test]
+async fn echo_loopback() {
+    let server = Bison::new().get("/echo", echo).serve("127.0.0.1:0");
+    let addr = server.local_addr();
+    tokio::spawn(server);
+
+    let mut request = format!("ws://{}/echo", addr).into_client_request().unwrap();
+    request
+        .headers_mut()
+        .insert("sec-websocket-protocol", "video, chat".parse().unwrap());
+
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (mut socket, response) = tokio_tungstenite::client_async(request, stream)
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::SWITCHING_PROTOCOLS);
+    assert_eq!(response.headers()[header::SEC_WEBSOCKET_PROTOCOL], "chat");
+    assert_eq!(
+        socket.next().await.unwrap().unwrap(),
+        ClientMessage::Text("chat".into())
+    );
+
+    socket
+        .send(ClientMessage::Text("hello".into()))
+        .await
+        .unwrap();
+    assert_eq!(
+        socket.next().await.unwrap().unwrap(),
+        ClientMessage::Text("hello".into())
+    );
+
+    socket
+        .send(ClientMessage::Binary(vec![1, 2, 3]))
+        .await
+        .unwrap();
+    assert_eq!(
+        socket.next().await.unwrap().unwrap(),
+        ClientMessage::Binary(vec![1, 2, 3])
+    );
+
+    socket.close(None).await.unwrap();
+}